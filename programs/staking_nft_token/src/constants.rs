@@ -4,3 +4,40 @@ use anchor_lang::prelude::*;
 pub const SEED: &str = "anchor";
 
 pub const ADMIN: Pubkey = pubkey!("DKbqMnDju2ftYBKM65DhPMLi7foVt5QPmbCmeeTk5eSN");
+
+pub const REWARD_DECIMALS: u8 = 6;
+
+// pool ids below this are reserved for admin pools, permissionless pools are numbered from it
+pub const COMMUNITY_POOL_ID_START: u64 = 1 << 32;
+
+// pool id of the config migrated from before pools existed, it still lives at [b"config"]
+pub const LEGACY_POOL_ID: u64 = u64::MAX;
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// fixed point scale of the SOL/SPL reward per share accumulator
+pub const ACC_PRECISION: u128 = 1_000_000_000_000;
+
+// rates on the config are paid out per day of staking, accrued by the second
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+// reward tokens a pool can pay on top of its primary reward
+pub const MAX_EXTRA_REWARDS: usize = 3;
+
+pub const PAUSE_STAKE_NFT: u8 = 1 << 0;
+pub const PAUSE_STAKE_SPL: u8 = 1 << 1;
+pub const PAUSE_STAKE_SOL: u8 = 1 << 2;
+pub const PAUSE_UNSTAKE_NFT: u8 = 1 << 3;
+pub const PAUSE_UNSTAKE_SPL: u8 = 1 << 4;
+pub const PAUSE_UNSTAKE_SOL: u8 = 1 << 5;
+// blocks every stake, unstake and claim path regardless of the per-action bits
+pub const PAUSE_FULL_FREEZE: u8 = 1 << 6;
+pub const PAUSE_CLAIM: u8 = 1 << 7;
+pub const PAUSE_ALL_FLAGS: u8 = PAUSE_STAKE_NFT
+    | PAUSE_STAKE_SPL
+    | PAUSE_STAKE_SOL
+    | PAUSE_UNSTAKE_NFT
+    | PAUSE_UNSTAKE_SPL
+    | PAUSE_UNSTAKE_SOL
+    | PAUSE_FULL_FREEZE
+    | PAUSE_CLAIM;
@@ -11,6 +11,132 @@ pub enum ErrorCode {
     #[msg("Invalid admin")]
     InvalidAdmin,
 
+    #[msg("No pending admin")]
+    NoPendingAdmin,
+
+    #[msg("Invalid pending admin")]
+    InvalidPendingAdmin,
+
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+
+    #[msg("Program is frozen")]
+    ProgramFrozen,
+
+    #[msg("NFT staking is paused")]
+    StakeNftPaused,
+
+    #[msg("SPL staking is paused")]
+    StakeSplPaused,
+
+    #[msg("SOL staking is paused")]
+    StakeSolPaused,
+
+    #[msg("NFT unstaking is paused")]
+    UnstakeNftPaused,
+
+    #[msg("SPL unstaking is paused")]
+    UnstakeSplPaused,
+
+    #[msg("SOL unstaking is paused")]
+    UnstakeSolPaused,
+
+    #[msg("Insufficient funds")]
+    InsufficientFunds,
+
+    #[msg("Collection is disabled")]
+    CollectionDisabled,
+
+    #[msg("Collection still has staked NFTs")]
+    CollectionInUse,
+
+    #[msg("SPL mint is disabled")]
+    SplMintDisabled,
+
+    #[msg("SPL mint still has staked tokens")]
+    SplMintInUse,
+
+    #[msg("Invalid reward weight")]
+    InvalidRewardWeight,
+
+    #[msg("Stake account is not owned by the user")]
+    InvalidStakeOwner,
+
+    #[msg("Missing or invalid accounts for this position")]
+    InvalidPositionAccounts,
+
+    #[msg("Invalid emission schedule")]
+    InvalidEmissionSchedule,
+
+    #[msg("Reward budget exhausted")]
+    RewardBudgetExhausted,
+
+    #[msg("Reward supply cap exceeded")]
+    RewardSupplyCapExceeded,
+
+    #[msg("Not supported in this reward mode")]
+    InvalidRewardMode,
+
+    #[msg("Reward treasury account is required")]
+    MissingRewardTreasury,
+
+    #[msg("Pool already pays the maximum number of reward tokens")]
+    TooManyRewardTokens,
+
+    #[msg("Reward token already registered on this pool")]
+    DuplicateRewardToken,
+
+    #[msg("Unknown reward token")]
+    InvalidRewardToken,
+
+    #[msg("Reward mint authority must be the pool config")]
+    InvalidRewardMintAuthority,
+
+    #[msg("Missing or mismatched reward token accounts")]
+    InvalidRewardAccounts,
+
+    #[msg("Early unstake penalty cannot exceed 100%")]
+    InvalidPenalty,
+
+    #[msg("Position is already unlocked, use the regular unstake")]
+    PositionNotLocked,
+
+    #[msg("Pool has an unbonding period, use request_unstake and withdraw")]
+    UnbondingRequired,
+
+    #[msg("Position is already unbonding")]
+    AlreadyUnbonding,
+
+    #[msg("Position has not requested an unstake")]
+    NotUnbonding,
+
+    #[msg("Unbonding cooldown has not passed")]
+    CooldownNotPassed,
+
+    #[msg("Invalid unstake amount")]
+    InvalidUnstakeAmount,
+
+    #[msg("Account already has the current layout")]
+    AccountAlreadyMigrated,
+
+    #[msg("Account layout not recognized")]
+    UnknownAccountLayout,
+
+    #[msg("User still has staked positions")]
+    UserHasOpenPositions,
+
+    #[msg("Pool id is reserved for permissionless pools")]
+    InvalidPoolId,
+
+    #[msg("Lock tiers must be at least 1x and grow with the lock duration")]
+    InvalidLockTiers,
+
+    #[msg("Invalid stake amount")]
+    InvalidStakeAmount,
+
+    #[msg("Claiming rewards is paused")]
+    ClaimPaused,
+
     #[msg("Over Flow")]
     Overflow,
 }
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::stake_config_account::StakeConfigAccount};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        let pending_admin = self.config.pending_admin.ok_or(ErrorCode::NoPendingAdmin)?;
+        require_keys_eq!(pending_admin, self.new_admin.key(), ErrorCode::InvalidPendingAdmin);
+
+        self.config.admin = pending_admin;
+        self.config.pending_admin = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::protocol_config_account::ProtocolConfigAccount};
+
+#[derive(Accounts)]
+pub struct AcceptProtocolAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfigAccount>,
+}
+
+impl<'info> AcceptProtocolAdmin<'info> {
+    pub fn accept_protocol_admin(&mut self) -> Result<()> {
+        let pending_admin = self.protocol_config.pending_admin.ok_or(ErrorCode::NoPendingAdmin)?;
+        require_keys_eq!(pending_admin, self.new_admin.key(), ErrorCode::InvalidPendingAdmin);
+
+        self.protocol_config.admin = pending_admin;
+        self.protocol_config.pending_admin = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{error::ErrorCode, state::{AllowedCollection, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct AddCollection<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub collection_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AllowedCollection::INIT_SPACE,
+        seeds = [b"collection", config.key().as_ref(), collection_mint.key().as_ref()],
+        bump
+    )]
+    pub allowed_collection: Account<'info, AllowedCollection>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddCollection<'info> {
+    pub fn add_collection(&mut self, points_per_nft_stake: Option<u8>, min_freeze_period: Option<u32>, bumps: &AddCollectionBumps) -> Result<()> {
+        self.config.update_indexes(Clock::get()?.unix_timestamp)?;
+
+        self.allowed_collection.set_inner(AllowedCollection {
+            config: self.config.key(),
+            collection_mint: self.collection_mint.key(),
+            enabled: true,
+            points_per_nft_stake,
+            min_freeze_period,
+            staked_count: 0,
+            staked_weight: 0,
+            reward_index: 0,
+            schedule_index_snapshot: self.config.schedule_index,
+            nft_index_snapshot: self.config.nft_reward_index,
+            bump: bumps.allowed_collection,
+        });
+        Ok(())
+    }
+}
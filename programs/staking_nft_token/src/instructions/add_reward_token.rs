@@ -0,0 +1,56 @@
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+use anchor_spl::token::Mint;
+
+use crate::{constants::MAX_EXTRA_REWARDS, error::ErrorCode, state::{RewardMode, RewardToken, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct AddRewardToken<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    pub reward_mint: Account<'info, Mint>,
+}
+
+impl<'info> AddRewardToken<'info> {
+    // extra tokens are emitted pro-rata over the SOL/SPL staked weight, NFT positions are paid
+    // per NFT out of the collection indexes and only ever earn the primary reward
+    pub fn add_reward_token(&mut self, mode: RewardMode, emission_per_second: u64, max_reward_supply: u64, reward_budget: u64) -> Result<()> {
+        require!(self.config.extra_rewards.len() < MAX_EXTRA_REWARDS, ErrorCode::TooManyRewardTokens);
+        let mint = self.reward_mint.key();
+        require!(
+            mint != self.config.reward_mint && self.config.extra_rewards.iter().all(|extra| extra.mint != mint),
+            ErrorCode::DuplicateRewardToken
+        );
+
+        require!(reward_budget <= max_reward_supply, ErrorCode::RewardSupplyCapExceeded);
+        match mode {
+            RewardMode::Mint => require!(
+                self.reward_mint.mint_authority == COption::Some(self.config.key()),
+                ErrorCode::InvalidRewardMintAuthority
+            ),
+            // treasury budgets only grow with deposits through fund_reward_token
+            RewardMode::Treasury => require!(reward_budget == 0, ErrorCode::InvalidRewardMode),
+        }
+
+        // settle the shared accrual first so the new token only emits from now on
+        self.config.update_pool(Clock::get()?.unix_timestamp)?;
+        self.config.extra_rewards.push(RewardToken {
+            mint,
+            mode,
+            emission_per_second,
+            acc_reward_per_share: 0,
+            max_reward_supply,
+            rewards_paid: 0,
+            reward_budget,
+            accrued_rewards: 0,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{error::ErrorCode, state::{AllowedSplMint, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct AddSplMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AllowedSplMint::INIT_SPACE,
+        seeds = [b"spl_mint", config.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub allowed_spl_mint: Account<'info, AllowedSplMint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddSplMint<'info> {
+    pub fn add_spl_mint(&mut self, reward_weight: u16, bumps: &AddSplMintBumps) -> Result<()> {
+        require!(reward_weight > 0, ErrorCode::InvalidRewardWeight);
+
+        let decimals_factor = 10u64.checked_pow(u32::from(self.mint.decimals)).ok_or(ErrorCode::Overflow)?;
+
+        self.allowed_spl_mint.set_inner(AllowedSplMint {
+            config: self.config.key(),
+            mint: self.mint.key(),
+            reward_weight,
+            decimals_factor,
+            enabled: true,
+            staked_amount: 0,
+            bump: bumps.allowed_spl_mint,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::stake_config_account::StakeConfigAccount};
+
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+}
+
+impl<'info> CancelAdminTransfer<'info> {
+    pub fn cancel_admin_transfer(&mut self) -> Result<()> {
+        require!(self.config.pending_admin.is_some(), ErrorCode::NoPendingAdmin);
+
+        self.config.pending_admin = None;
+        Ok(())
+    }
+}
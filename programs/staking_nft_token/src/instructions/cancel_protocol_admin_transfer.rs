@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::protocol_config_account::ProtocolConfigAccount};
+
+#[derive(Accounts)]
+pub struct CancelProtocolAdminTransfer<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"protocol"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfigAccount>,
+}
+
+impl<'info> CancelProtocolAdminTransfer<'info> {
+    pub fn cancel_protocol_admin_transfer(&mut self) -> Result<()> {
+        require!(self.protocol_config.pending_admin.is_some(), ErrorCode::NoPendingAdmin);
+
+        self.protocol_config.pending_admin = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::{AllowedCollection, StakeConfigAccount, UserAccount, StakeAccount, StakeKind};
+use crate::error::ErrorCode;
+use crate::utils::{pay_rewards, settle_extra_rewards, Shortfall};
+use crate::constants::PAUSE_CLAIM;
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        address = config.reward_mint,
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = user,
+    )]
+    pub user_reward_ata: Account<'info, TokenAccount>,
+
+    // only used by pools paying rewards out of a treasury
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+    )]
+    pub reward_treasury: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = config,
+        constraint = stake_account.owner == user.key() @ ErrorCode::InvalidStakeOwner,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    // NFT positions: the allowlist entry the NFT was staked under
+    #[account(
+        mut,
+        has_one = config,
+        constraint = Some(allowed_collection.collection_mint) == stake_account.collection @ ErrorCode::InvalidPositionAccounts,
+    )]
+    pub allowed_collection: Option<Account<'info, AllowedCollection>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimRewards<'info> {
+    pub fn claim_rewards(&mut self, reward_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        self.config.require_not_paused(PAUSE_CLAIM, ErrorCode::ClaimPaused)?;
+
+        let current = Clock::get()?.unix_timestamp;
+
+        let reward_index = self.reward_index(current)?;
+        let mut reward_amount = self.stake_account.pending_rewards(reward_index)?;
+        if self.stake_account.kind == StakeKind::Nft {
+            reward_amount = self.stake_account.apply_multiplier(reward_amount)?;
+        }
+        self.config.release_accrued_rewards(reward_amount);
+        if reward_amount > 0 {
+            pay_rewards(
+                &mut self.config,
+                &self.reward_mint,
+                self.reward_treasury.as_ref(),
+                &self.user_reward_ata,
+                &self.token_program,
+                reward_amount,
+            )?;
+            self.user_account.points = self.user_account.points.checked_add(reward_amount).ok_or(ErrorCode::Overflow)?;
+        }
+        self.stake_account.last_claimed_at = current;
+        self.stake_account.checkpoint_rewards(reward_index)?;
+
+        // NFT positions never earn extra reward tokens, see add_reward_token
+        if self.stake_account.kind != StakeKind::Nft {
+            settle_extra_rewards(&mut self.config, &mut self.stake_account, reward_accounts, &self.token_program, Shortfall::Defer)?;
+        }
+        Ok(())
+    }
+
+    // brings the index the position accrues against up to now and returns it
+    fn reward_index(&mut self, current: i64) -> Result<u128> {
+        if self.stake_account.kind == StakeKind::Nft {
+            let allowed_collection = self.allowed_collection.as_mut().ok_or(ErrorCode::InvalidPositionAccounts)?;
+            self.config.update_indexes(current)?;
+            allowed_collection.update_index(&self.config)?;
+            return Ok(allowed_collection.reward_index);
+        }
+
+        self.config.update_pool(current)?;
+        Ok(self.config.acc_reward_per_share)
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::UserAccount};
+
+#[derive(Accounts)]
+pub struct CloseUser<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"user", user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+}
+
+impl<'info> CloseUser<'info> {
+    // rewards only accrue on open positions and are settled when they close, so with
+    // nothing staked there is nothing left pending either
+    pub fn close_user(&mut self) -> Result<()> {
+        require!(
+            self.user_account.nft_staked_amount == 0
+                && self.user_account.spl_staked_amount == 0
+                && self.user_account.sol_staked_amount == 0,
+            ErrorCode::UserHasOpenPositions
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::token::{Mint, Token};
+
+use crate::{constants::REWARD_DECIMALS, error::ErrorCode, state::{PoolParams, ProtocolConfigAccount, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct CreatePool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = protocol_config.treasury_bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + StakeConfigAccount::INIT_SPACE,
+        seeds = [b"config", protocol_config.next_pool_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"rewards", config.key().as_ref()],
+        bump,
+        mint::decimals = REWARD_DECIMALS,
+        mint::authority = config
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreatePool<'info> {
+    pub fn create_pool(
+        &mut self,
+        params: PoolParams,
+        bumps: &CreatePoolBumps,
+    ) -> Result<()> {
+        let fee = self.protocol_config.pool_creation_fee;
+        if fee > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.creator.to_account_info(),
+                to: self.treasury.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            transfer(cpi_ctx, fee)?;
+        }
+
+        params.schedule.validate()?;
+        require!(params.reward_budget <= params.max_reward_supply, ErrorCode::RewardSupplyCapExceeded);
+
+        // ids are handed out by the protocol so a pool id can't be claimed ahead of its creator
+        let pool_id = self.protocol_config.next_pool_id;
+        self.protocol_config.next_pool_id = pool_id.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+        self.config.set_inner(StakeConfigAccount::new(
+            self.creator.key(),
+            pool_id,
+            params,
+            self.reward_mint.key(),
+            bumps.reward_mint,
+            bumps.config,
+            Clock::get()?.unix_timestamp,
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{associated_token::AssociatedToken, token::{Mint, Token, TokenAccount}};
+
+use crate::{error::ErrorCode, state::{PoolParams, ProtocolConfigAccount, RewardMode, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct CreateTreasuryPool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = protocol_config.treasury_bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + StakeConfigAccount::INIT_SPACE,
+        seeds = [b"config", protocol_config.next_pool_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    // existing token the pool pays out, the program never needs its mint authority
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+    )]
+    pub reward_treasury: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateTreasuryPool<'info> {
+    pub fn create_treasury_pool(
+        &mut self,
+        params: PoolParams,
+        bumps: &CreateTreasuryPoolBumps,
+    ) -> Result<()> {
+        let fee = self.protocol_config.pool_creation_fee;
+        if fee > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.creator.to_account_info(),
+                to: self.treasury.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            transfer(cpi_ctx, fee)?;
+        }
+
+        params.schedule.validate()?;
+        // the budget of a treasury pool only grows with deposits through fund_rewards
+        require!(params.reward_budget == 0, ErrorCode::InvalidRewardMode);
+
+        // ids are handed out by the protocol so a pool id can't be claimed ahead of its creator
+        let pool_id = self.protocol_config.next_pool_id;
+        self.protocol_config.next_pool_id = pool_id.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+        let mut config = StakeConfigAccount::new(
+            self.creator.key(),
+            pool_id,
+            params,
+            self.reward_mint.key(),
+            0,
+            bumps.config,
+            Clock::get()?.unix_timestamp,
+        );
+        config.reward_mode = RewardMode::Treasury;
+        self.config.set_inner(config);
+        Ok(())
+    }
+}
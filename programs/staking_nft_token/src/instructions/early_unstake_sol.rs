@@ -0,0 +1,110 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+
+use crate::state::{StakeConfigAccount, UserAccount, StakeAccount, StakeKind};
+use crate::error::ErrorCode;
+use crate::constants::PAUSE_UNSTAKE_SOL;
+
+#[derive(Accounts)]
+pub struct EarlyUnstakeSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = config,
+        constraint = stake_account.kind == StakeKind::Sol @ ErrorCode::InvalidPositionAccounts,
+        seeds = [b"stake", config.key().as_ref(), user.key().as_ref(), &stake_account.seed.to_le_bytes()],
+        bump = stake_account.bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", stake_account.key().as_ref()],
+        bump = stake_account.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"penalty", config.key().as_ref()],
+        bump
+    )]
+    pub penalty_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EarlyUnstakeSol<'info> {
+    // leaves a locked position, everything it accrued is forfeited and the pool's penalty is
+    // kept out of the principal; in pools with an unbonding period the rest waits out the
+    // same cooldown as a regular exit and is paid by withdraw_sol
+    pub fn early_unstake_sol(&mut self) -> Result<()> {
+        self.config.require_not_paused(PAUSE_UNSTAKE_SOL, ErrorCode::UnstakeSolPaused)?;
+
+        require!(self.stake_account.withdrawable_at == 0, ErrorCode::AlreadyUnbonding);
+
+        let current = Clock::get()?.unix_timestamp;
+        require!(current < self.stake_account.unlock_at, ErrorCode::PositionNotLocked);
+
+        self.config.update_pool(current)?;
+        self.config.forfeit_rewards(&self.stake_account)?;
+        self.config.total_staked_weight = self.config.total_staked_weight.checked_sub(self.stake_account.weight).ok_or(ErrorCode::Overflow)?;
+        self.stake_account.weight = 0;
+        self.stake_account.reward_debt = 0;
+        self.stake_account.extra_reward_debts.clear();
+
+        let amount = self.stake_account.amount;
+        let penalty = self.config.early_unstake_penalty(amount)?;
+        let payout = self.vault.lamports() - penalty;
+
+        let seeds = &[
+            b"vault",
+            self.stake_account.to_account_info().key.as_ref(),
+            &[self.stake_account.vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        if penalty > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: self.penalty_vault.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            transfer(cpi_ctx, penalty)?;
+        }
+
+        if self.config.unbonding_period > 0 {
+            self.stake_account.amount = amount - penalty;
+            self.stake_account.withdrawable_at = current.checked_add(i64::from(self.config.unbonding_period)).ok_or(ErrorCode::Overflow)?;
+            self.user_account.sol_staked_amount = self.user_account.sol_staked_amount.checked_sub(penalty).ok_or(ErrorCode::Overflow)?;
+            return Ok(());
+        }
+
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        transfer(cpi_ctx, payout)?;
+
+        self.user_account.sol_staked_amount = self.user_account.sol_staked_amount.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+        self.stake_account.close(self.user.to_account_info())
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{error::ErrorCode, state::{RewardMode, StakeConfigAccount}};
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct FundRewardToken<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        constraint = config.extra_rewards.get(usize::from(index)).map(|extra| extra.mint) == Some(reward_mint.key()) @ ErrorCode::InvalidRewardToken,
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = funder,
+    )]
+    pub funder_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+    )]
+    pub reward_treasury: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> FundRewardToken<'info> {
+    pub fn fund_reward_token(&mut self, index: u8, amount: u64) -> Result<()> {
+        let extra = &mut self.config.extra_rewards[usize::from(index)];
+        require!(extra.mode == RewardMode::Treasury, ErrorCode::InvalidRewardMode);
+        extra.top_up_reward_budget(amount)?;
+
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.funder_ata.to_account_info(),
+            mint: self.reward_mint.to_account_info(),
+            to: self.reward_treasury.to_account_info(),
+            authority: self.funder.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        transfer_checked(cpi_ctx, amount, self.reward_mint.decimals)
+    }
+}
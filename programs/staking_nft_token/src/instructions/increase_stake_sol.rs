@@ -0,0 +1,106 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::{apply_multiplier, StakeConfigAccount, UserAccount, StakeAccount, StakeKind};
+use crate::error::ErrorCode;
+use crate::utils::{settle_position, RewardPayout, Shortfall};
+use crate::constants::PAUSE_STAKE_SOL;
+
+#[derive(Accounts)]
+pub struct IncreaseStakeSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        address = config.reward_mint,
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = user,
+    )]
+    pub user_reward_ata: Account<'info, TokenAccount>,
+
+    // only used by pools paying rewards out of a treasury
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+    )]
+    pub reward_treasury: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = config,
+        constraint = stake_account.kind == StakeKind::Sol @ ErrorCode::InvalidPositionAccounts,
+        seeds = [b"stake", config.key().as_ref(), user.key().as_ref(), &stake_account.seed.to_le_bytes()],
+        bump = stake_account.bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", stake_account.key().as_ref()],
+        bump = stake_account.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> IncreaseStakeSol<'info> {
+    pub fn increase_stake_sol(&mut self, amount: u64, reward_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        self.config.require_not_paused(PAUSE_STAKE_SOL, ErrorCode::StakeSolPaused)?;
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+        require!(self.stake_account.withdrawable_at == 0, ErrorCode::AlreadyUnbonding);
+
+        let current = Clock::get()?.unix_timestamp;
+
+        // pay out what the position earned at its old weight before it changes, in full: a top-up
+        // waits for the budget to cover it rather than forfeit earned rewards
+        let payout = RewardPayout {
+            reward_mint: &self.reward_mint,
+            reward_treasury: self.reward_treasury.as_ref(),
+            to: &self.user_reward_ata,
+            token_program: &self.token_program,
+        };
+        settle_position(&mut self.config, &mut self.stake_account, &mut self.user_account, payout, reward_accounts, current, Shortfall::Reject)?;
+
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: self.user.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        transfer(cpi_ctx, amount)?;
+
+        let weight = apply_multiplier(self.config.sol_stake_weight(amount)?, self.stake_account.multiplier_bps)?;
+        self.config.total_staked_weight = self.config.total_staked_weight.checked_add(weight).ok_or(ErrorCode::Overflow)?;
+        self.stake_account.weight = self.stake_account.weight.checked_add(weight).ok_or(ErrorCode::Overflow)?;
+        self.stake_account.extend_lock(current, amount)?;
+        self.stake_account.amount = self.stake_account.amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        self.stake_account.checkpoint_rewards(self.config.acc_reward_per_share)?;
+        self.stake_account.checkpoint_extra_rewards(&self.config.extra_rewards)?;
+
+        self.user_account.sol_staked_amount = self.user_account.sol_staked_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
@@ -1,18 +1,21 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint,Token};
 
-use crate::state::stake_config_account::StakeConfigAccount;
+use crate::{constants::{ADMIN, COMMUNITY_POOL_ID_START, REWARD_DECIMALS}, error::ErrorCode, state::stake_config_account::{PoolParams, StakeConfigAccount}};
 
 #[derive(Accounts)]
-
+#[instruction(pool_id: u64)]
 pub struct InitializeConfig<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        address = ADMIN @ ErrorCode::InvalidAdmin
+    )]
     pub admin: Signer<'info>,
     #[account(
         init,
         payer = admin,
         space = 8 + StakeConfigAccount::INIT_SPACE,
-        seeds = [b"config"],
+        seeds = [b"config", pool_id.to_le_bytes().as_ref()],
         bump
     )]
     pub config: Account<'info,StakeConfigAccount>,
@@ -22,7 +25,7 @@ pub struct InitializeConfig<'info> {
         payer = admin,
         seeds = [b"rewards", config.key().as_ref()],
         bump,
-        mint::decimals = 6,
+        mint::decimals = REWARD_DECIMALS,
         mint::authority = config
     )]
     pub reward_mint: Account<'info,Mint>,
@@ -32,16 +35,20 @@ pub struct InitializeConfig<'info> {
 }
 
 impl<'info> InitializeConfig<'info> {
-    pub fn initialize_config(&mut self,points_per_nft_stake: u8,points_per_spl_stake: u8,points_per_sol_stake: u8,min_freeze_period: u32,bumps: &InitializeConfigBumps) -> Result<()> {
-        self.config.set_inner(StakeConfigAccount{
-            points_per_nft_stake,
-            points_per_sol_stake,
-            points_per_spl_stake,
-            min_freeze_period,
-            reward_bump: bumps.reward_mint,
-            bump: bumps.config,  
-        });
+    pub fn initialize_config(&mut self,pool_id: u64,params: PoolParams,bumps: &InitializeConfigBumps) -> Result<()> {
+        require!(pool_id < COMMUNITY_POOL_ID_START, ErrorCode::InvalidPoolId);
+        params.schedule.validate()?;
+        require!(params.reward_budget <= params.max_reward_supply, ErrorCode::RewardSupplyCapExceeded);
+
+        self.config.set_inner(StakeConfigAccount::new(
+            self.admin.key(),
+            pool_id,
+            params,
+            self.reward_mint.key(),
+            bumps.reward_mint,
+            bumps.config,
+            Clock::get()?.unix_timestamp,
+        ));
         Ok(())
     }
 }
-
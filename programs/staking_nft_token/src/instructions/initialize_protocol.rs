@@ -0,0 +1,57 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+
+use crate::{constants::{ADMIN, COMMUNITY_POOL_ID_START}, error::ErrorCode, state::protocol_config_account::ProtocolConfigAccount};
+
+#[derive(Accounts)]
+pub struct InitializeProtocol<'info> {
+    #[account(
+        mut,
+        address = ADMIN @ ErrorCode::InvalidAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProtocolConfigAccount::INIT_SPACE,
+        seeds = [b"protocol"],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeProtocol<'info> {
+    pub fn initialize_protocol(&mut self, pool_creation_fee: u64, bumps: &InitializeProtocolBumps) -> Result<()> {
+        // fund the treasury up to rent exemption so fees of any size can be transferred into it
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        let missing = rent_exempt.saturating_sub(self.treasury.lamports());
+        if missing > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.admin.to_account_info(),
+                to: self.treasury.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            transfer(cpi_ctx, missing)?;
+        }
+
+        self.protocol_config.set_inner(ProtocolConfigAccount {
+            admin: self.admin.key(),
+            pending_admin: None,
+            pool_creation_fee,
+            next_pool_id: COMMUNITY_POOL_ID_START,
+            treasury_bump: bumps.treasury,
+            bump: bumps.protocol_config,
+        });
+        Ok(())
+    }
+}
@@ -22,11 +22,13 @@ pub struct InitializeUser<'info> {
 impl<'info> InitializeUser<'info> {
     pub fn initialize_user(&mut self, bump: &InitializeUserBumps) -> Result<()> {
         self.user_account.set_inner(UserAccount { 
+            version: UserAccount::VERSION,
             points: 0, 
             nft_staked_amount: 0, 
             spl_staked_amount: 0, 
             sol_staked_amount: 0, 
-            bump:  bump.user_account
+            bump:  bump.user_account,
+            reserved: [0; 32],
         });
         Ok(())
     }
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{constants::ADMIN, error::ErrorCode, state::{StakeConfigAccount, StakeConfigAccountV0}, utils::{migrate_account, read_legacy}};
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(
+        mut,
+        address = ADMIN @ ErrorCode::InvalidAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: still in a legacy layout, owner and discriminator are checked by read_legacy
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"rewards", config.key().as_ref()],
+        bump
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateConfig<'info> {
+    pub fn migrate_config(&mut self) -> Result<()> {
+        let space = 8 + StakeConfigAccount::INIT_SPACE;
+        let legacy: StakeConfigAccountV0 = read_legacy(
+            &self.config,
+            StakeConfigAccount::DISCRIMINATOR,
+            8 + StakeConfigAccountV0::INIT_SPACE,
+            space,
+        )?;
+
+        // everything minted so far was paid by the legacy pool
+        let config = legacy.migrate(self.reward_mint.key(), self.reward_mint.supply, Clock::get()?.unix_timestamp);
+        migrate_account(&self.config, &config, space, &self.admin, &self.system_program)
+    }
+}
@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    metadata::{mpl_token_metadata::accounts::Metadata, MetadataAccount},
+    token::{spl_token::native_mint, Token, TokenAccount},
+};
+
+use crate::{
+    constants::{BPS_DENOMINATOR, LEGACY_POOL_ID},
+    error::ErrorCode,
+    state::{AllowedCollection, AllowedSplMint, StakeAccount, StakeAccountV0, StakeConfigAccount, StakeKind, UserAccount},
+    utils::{close_legacy, migrate_account, read_legacy},
+};
+
+#[derive(Accounts)]
+pub struct MigrateStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: still in a legacy layout, owner and discriminator are checked by read_legacy and
+    /// the address against the legacy seeds by legacy_kind
+    #[account(mut)]
+    pub stake_account: UncheckedAccount<'info>,
+
+    // the legacy pool, which has to be migrated first
+    #[account(
+        mut,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump,
+        constraint = config.pool_id == LEGACY_POOL_ID @ ErrorCode::InvalidPositionAccounts
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    /// CHECK: principal of a SOL or SPL position, its [b"vault", stake_account] PDA or token
+    /// account, checked against the position in migrate_stake; not passed for NFTs
+    pub vault: Option<UncheckedAccount<'info>>,
+
+    // allowlist entry of the staked mint, SPL positions only
+    #[account(
+        mut,
+        has_one = config
+    )]
+    pub allowed_spl_mint: Option<Account<'info, AllowedSplMint>>,
+
+    // metadata of the staked NFT and allowlist entry of its collection, NFT positions only
+    pub metadata: Option<Account<'info, MetadataAccount>>,
+
+    #[account(
+        mut,
+        has_one = config
+    )]
+    pub allowed_collection: Option<Account<'info, AllowedCollection>>,
+
+    // already migrated, its staked totals are rebuilt from the positions migrated into it
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateStake<'info> {
+    // legacy positions were paid their reward up front when staked, so they earn from now
+    // on, at 1x and still locked for the pool freeze period from when they were staked.
+    // Legacy SOL/SPL exits emptied the vault but left the position open, those are closed
+    // and their rent refunded instead
+    pub fn migrate_stake(&mut self) -> Result<()> {
+        let space = 8 + StakeAccount::INIT_SPACE;
+        let legacy: StakeAccountV0 = read_legacy(
+            &self.stake_account,
+            StakeAccount::DISCRIMINATOR,
+            8 + StakeAccountV0::INIT_SPACE,
+            space,
+        )?;
+        require_keys_eq!(legacy.owner, self.user.key(), ErrorCode::InvalidStakeOwner);
+
+        let kind = self.legacy_kind(&legacy)?;
+        let now = Clock::get()?.unix_timestamp;
+        let mut stake_account = StakeAccount {
+            version: StakeAccount::VERSION,
+            owner: legacy.owner,
+            config: self.config.key(),
+            kind,
+            mint: legacy.mint,
+            collection: None,
+            staked_at: legacy.staked_at,
+            last_claimed_at: now,
+            unlock_at: 0,
+            multiplier_bps: BPS_DENOMINATOR as u16,
+            withdrawable_at: 0,
+            weight: 0,
+            reward_debt: 0,
+            amount: 0,
+            extra_reward_debts: Vec::new(),
+            bump: legacy.bump,
+            vault_bump: legacy.vault_bump,
+            seed: legacy.seed,
+            legacy_seeds: kind == StakeKind::Spl,
+            lock_duration: 0,
+            reserved: [0; 27],
+        };
+
+        match kind {
+            StakeKind::Sol => self.migrate_sol(&mut stake_account, now)?,
+            StakeKind::Spl => self.migrate_spl(&mut stake_account, now)?,
+            StakeKind::Nft => self.migrate_nft(&mut stake_account, now)?,
+        }
+        if stake_account.amount == 0 {
+            return close_legacy(&self.stake_account, &self.user);
+        }
+
+        let user_account = &mut self.user_account;
+        let staked_amount = match kind {
+            StakeKind::Sol => &mut user_account.sol_staked_amount,
+            StakeKind::Spl => &mut user_account.spl_staked_amount,
+            StakeKind::Nft => &mut user_account.nft_staked_amount,
+        };
+        *staked_amount = staked_amount.checked_add(stake_account.amount).ok_or(ErrorCode::Overflow)?;
+
+        migrate_account(&self.stake_account, &stake_account, space, &self.user, &self.system_program)
+    }
+
+    // a legacy position only records its kind in the seeds of its address:
+    // SOL [b"stake", config, owner, seed], NFT [b"stake", config, mint, seed] and
+    // SPL [b"stake", config, owner, mint]
+    fn legacy_kind(&self, legacy: &StakeAccountV0) -> Result<StakeKind> {
+        let config = self.config.key();
+        let seed = legacy.seed.to_le_bytes();
+        let bump = [legacy.bump];
+        let candidates: [(StakeKind, &[u8], &[u8]); 3] = [
+            (StakeKind::Sol, legacy.owner.as_ref(), &seed),
+            (StakeKind::Nft, legacy.mint.as_ref(), &seed),
+            (StakeKind::Spl, legacy.owner.as_ref(), legacy.mint.as_ref()),
+        ];
+        let kind = candidates
+            .into_iter()
+            .find(|(_, first, second)| {
+                Pubkey::create_program_address(&[b"stake", config.as_ref(), first, second, &bump], &crate::ID)
+                    .is_ok_and(|address| address == self.stake_account.key())
+            })
+            .map(|(kind, _, _)| kind)
+            .ok_or(ErrorCode::UnknownAccountLayout)?;
+        Ok(kind)
+    }
+
+    fn migrate_sol(&mut self, stake_account: &mut StakeAccount, now: i64) -> Result<()> {
+        require_keys_eq!(stake_account.mint, native_mint::id(), ErrorCode::UnknownAccountLayout);
+        let vault = self.vault.as_ref().ok_or(ErrorCode::InvalidPositionAccounts)?;
+        let expected = Pubkey::create_program_address(
+            &[b"vault", self.stake_account.key().as_ref(), &[stake_account.vault_bump]],
+            &crate::ID,
+        )
+        .or(Err(ErrorCode::InvalidPositionAccounts))?;
+        require_keys_eq!(vault.key(), expected, ErrorCode::InvalidPositionAccounts);
+
+        stake_account.amount = vault.lamports();
+        if stake_account.amount == 0 {
+            return Ok(());
+        }
+        let weight = self.config.sol_stake_weight(stake_account.amount)?;
+        self.add_pool_weight(stake_account, weight, now)
+    }
+
+    fn migrate_spl(&mut self, stake_account: &mut StakeAccount, now: i64) -> Result<()> {
+        let vault = self.vault.as_ref().ok_or(ErrorCode::InvalidPositionAccounts)?;
+        require_keys_eq!(
+            vault.key(),
+            get_associated_token_address(&self.stake_account.key(), &stake_account.mint),
+            ErrorCode::InvalidPositionAccounts
+        );
+        // legacy unstake_spl closed the vault on exit
+        if vault.lamports() > 0 {
+            require_keys_eq!(*vault.owner, Token::id(), ErrorCode::InvalidPositionAccounts);
+            stake_account.amount = TokenAccount::try_deserialize(&mut &vault.try_borrow_data()?[..])?.amount;
+        }
+        if stake_account.amount == 0 {
+            return Ok(());
+        }
+
+        let allowed_spl_mint = self.allowed_spl_mint.as_mut().ok_or(ErrorCode::InvalidPositionAccounts)?;
+        require_keys_eq!(allowed_spl_mint.mint, stake_account.mint, ErrorCode::InvalidPositionAccounts);
+        allowed_spl_mint.staked_amount = allowed_spl_mint.staked_amount.checked_add(stake_account.amount).ok_or(ErrorCode::Overflow)?;
+        let weight = allowed_spl_mint.stake_weight(&self.config, stake_account.amount)?;
+        self.add_pool_weight(stake_account, weight, now)
+    }
+
+    fn add_pool_weight(&mut self, stake_account: &mut StakeAccount, weight: u64, now: i64) -> Result<()> {
+        self.config.update_pool(now)?;
+        self.config.total_staked_weight = self.config.total_staked_weight.checked_add(weight).ok_or(ErrorCode::Overflow)?;
+
+        stake_account.lock_duration = self.config.min_freeze_period;
+        stake_account.unlock_at = stake_account.staked_at.checked_add(i64::from(stake_account.lock_duration)).ok_or(ErrorCode::Overflow)?;
+        stake_account.weight = weight;
+        stake_account.checkpoint_rewards(self.config.acc_reward_per_share)?;
+        stake_account.checkpoint_extra_rewards(&self.config.extra_rewards)
+    }
+
+    fn migrate_nft(&mut self, stake_account: &mut StakeAccount, now: i64) -> Result<()> {
+        let metadata = self.metadata.as_ref().ok_or(ErrorCode::InvalidPositionAccounts)?;
+        require_keys_eq!(metadata.key(), Metadata::find_pda(&stake_account.mint).0, ErrorCode::InvalidPositionAccounts);
+        let collection = metadata
+            .collection
+            .as_ref()
+            .filter(|collection| collection.verified)
+            .ok_or(ErrorCode::InvalidPositionAccounts)?;
+
+        let allowed_collection = self.allowed_collection.as_mut().ok_or(ErrorCode::InvalidPositionAccounts)?;
+        require_keys_eq!(allowed_collection.collection_mint, collection.key, ErrorCode::InvalidPositionAccounts);
+
+        self.config.update_indexes(now)?;
+        allowed_collection.update_index(&self.config)?;
+
+        stake_account.collection = Some(collection.key);
+        stake_account.lock_duration = allowed_collection.min_freeze_period(&self.config);
+        stake_account.unlock_at = stake_account.staked_at.checked_add(i64::from(stake_account.lock_duration)).ok_or(ErrorCode::Overflow)?;
+        stake_account.weight = 1;
+        stake_account.amount = 1;
+        stake_account.checkpoint_rewards(allowed_collection.reward_index)?;
+
+        let weight = u64::from(stake_account.multiplier_bps);
+        allowed_collection.staked_count = allowed_collection.staked_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        allowed_collection.staked_weight = allowed_collection.staked_weight.checked_add(weight).ok_or(ErrorCode::Overflow)?;
+        self.config.add_nft_weight(allowed_collection.points_per_nft_stake, weight)
+    }
+}
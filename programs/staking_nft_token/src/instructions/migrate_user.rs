@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::{UserAccount, UserAccountV0}, utils::{migrate_account, read_legacy}};
+
+#[derive(Accounts)]
+pub struct MigrateUser<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: still in a legacy layout, owner and discriminator are checked by read_legacy
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump
+    )]
+    pub user_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateUser<'info> {
+    pub fn migrate_user(&mut self) -> Result<()> {
+        let space = 8 + UserAccount::INIT_SPACE;
+        let legacy: UserAccountV0 = read_legacy(
+            &self.user_account,
+            UserAccount::DISCRIMINATOR,
+            8 + UserAccountV0::INIT_SPACE,
+            space,
+        )?;
+
+        let user_account = UserAccount::from(legacy);
+        migrate_account(&self.user_account, &user_account, space, &self.user, &self.system_program)
+    }
+}
@@ -6,6 +6,53 @@ pub mod unstake_spl;
 pub mod stake_spl;
 pub mod stake_nft;
 pub mod unstake_nft;
+pub mod claim_rewards;
+pub mod update_config;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod cancel_admin_transfer;
+pub mod set_paused;
+pub mod set_emission_schedule;
+pub mod top_up_reward_budget;
+pub mod initialize_protocol;
+pub mod update_protocol;
+pub mod withdraw_protocol_fees;
+pub mod propose_protocol_admin;
+pub mod accept_protocol_admin;
+pub mod cancel_protocol_admin_transfer;
+pub mod create_pool;
+pub mod create_treasury_pool;
+pub mod fund_rewards;
+pub mod withdraw_unallocated_rewards;
+pub mod add_reward_token;
+pub mod update_reward_token;
+pub mod fund_reward_token;
+pub mod top_up_reward_token_budget;
+pub mod withdraw_unallocated_reward_token;
+pub mod set_lock_tiers;
+pub mod set_early_unstake_penalty;
+pub mod withdraw_sol_penalties;
+pub mod withdraw_spl_penalties;
+pub mod early_unstake_sol;
+pub mod early_unstake_spl;
+pub mod set_unbonding_period;
+pub mod request_unstake;
+pub mod withdraw_sol;
+pub mod withdraw_spl;
+pub mod increase_stake_sol;
+pub mod increase_stake_spl;
+pub mod migrate_config;
+pub mod migrate_user;
+pub mod migrate_stake;
+pub mod close_user;
+pub mod add_collection;
+pub mod set_collection_enabled;
+pub mod remove_collection;
+pub mod update_collection;
+pub mod add_spl_mint;
+pub mod set_spl_mint_enabled;
+pub mod update_spl_mint;
+pub mod remove_spl_mint;
 
 pub use initialize_config::*;
 pub use initialize_user::*;
@@ -15,4 +62,51 @@ pub use unstake_sol::*;
 pub use unstake_spl::*;
 pub use stake_nft::*;
 pub use unstake_nft::*;
+pub use claim_rewards::*;
+pub use update_config::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use cancel_admin_transfer::*;
+pub use set_paused::*;
+pub use set_emission_schedule::*;
+pub use top_up_reward_budget::*;
+pub use initialize_protocol::*;
+pub use update_protocol::*;
+pub use withdraw_protocol_fees::*;
+pub use propose_protocol_admin::*;
+pub use accept_protocol_admin::*;
+pub use cancel_protocol_admin_transfer::*;
+pub use create_pool::*;
+pub use create_treasury_pool::*;
+pub use fund_rewards::*;
+pub use withdraw_unallocated_rewards::*;
+pub use add_reward_token::*;
+pub use update_reward_token::*;
+pub use fund_reward_token::*;
+pub use top_up_reward_token_budget::*;
+pub use withdraw_unallocated_reward_token::*;
+pub use set_lock_tiers::*;
+pub use set_early_unstake_penalty::*;
+pub use withdraw_sol_penalties::*;
+pub use withdraw_spl_penalties::*;
+pub use early_unstake_sol::*;
+pub use early_unstake_spl::*;
+pub use set_unbonding_period::*;
+pub use request_unstake::*;
+pub use withdraw_sol::*;
+pub use withdraw_spl::*;
+pub use increase_stake_sol::*;
+pub use increase_stake_spl::*;
+pub use migrate_config::*;
+pub use migrate_user::*;
+pub use migrate_stake::*;
+pub use close_user::*;
+pub use add_collection::*;
+pub use set_collection_enabled::*;
+pub use remove_collection::*;
+pub use update_collection::*;
+pub use add_spl_mint::*;
+pub use set_spl_mint_enabled::*;
+pub use update_spl_mint::*;
+pub use remove_spl_mint::*;
 
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::stake_config_account::StakeConfigAccount};
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+}
+
+impl<'info> ProposeAdmin<'info> {
+    // the new admin only takes over once it signs accept_admin, so a wrong key can be replaced or cancelled
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        self.config.pending_admin = Some(new_admin);
+        Ok(())
+    }
+}
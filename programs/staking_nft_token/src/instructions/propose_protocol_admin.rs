@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::protocol_config_account::ProtocolConfigAccount};
+
+#[derive(Accounts)]
+pub struct ProposeProtocolAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"protocol"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfigAccount>,
+}
+
+impl<'info> ProposeProtocolAdmin<'info> {
+    // same two step handover as pool admins, the new admin has to sign accept_protocol_admin
+    pub fn propose_protocol_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        self.protocol_config.pending_admin = Some(new_admin);
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::{AllowedCollection, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct RemoveCollection<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        close = admin,
+        has_one = config,
+        seeds = [b"collection", config.key().as_ref(), allowed_collection.collection_mint.as_ref()],
+        bump = allowed_collection.bump
+    )]
+    pub allowed_collection: Account<'info, AllowedCollection>,
+}
+
+impl<'info> RemoveCollection<'info> {
+    pub fn remove_collection(&mut self) -> Result<()> {
+        // staked NFTs need the entry to be unstaked, so it can only go once they are all out
+        require!(self.allowed_collection.staked_count == 0, ErrorCode::CollectionInUse);
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::{AllowedSplMint, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct RemoveSplMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        close = admin,
+        has_one = config,
+        seeds = [b"spl_mint", config.key().as_ref(), allowed_spl_mint.mint.as_ref()],
+        bump = allowed_spl_mint.bump
+    )]
+    pub allowed_spl_mint: Account<'info, AllowedSplMint>,
+}
+
+impl<'info> RemoveSplMint<'info> {
+    pub fn remove_spl_mint(&mut self) -> Result<()> {
+        require!(self.allowed_spl_mint.staked_amount == 0, ErrorCode::SplMintInUse);
+        Ok(())
+    }
+}
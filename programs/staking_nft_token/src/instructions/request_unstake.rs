@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::{StakeConfigAccount, UserAccount, StakeAccount, StakeKind};
+use crate::error::ErrorCode;
+use crate::utils::{settle_position, RewardPayout, Shortfall};
+use crate::constants::{PAUSE_UNSTAKE_SOL, PAUSE_UNSTAKE_SPL};
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        address = config.reward_mint,
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = user,
+    )]
+    pub user_reward_ata: Account<'info, TokenAccount>,
+
+    // only used by pools paying rewards out of a treasury
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+    )]
+    pub reward_treasury: Option<Account<'info, TokenAccount>>,
+
+    // SOL and SPL positions only, staked NFTs never leave the owner's wallet
+    #[account(
+        mut,
+        has_one = config,
+        constraint = stake_account.owner == user.key() @ ErrorCode::InvalidStakeOwner,
+        constraint = stake_account.kind != StakeKind::Nft @ ErrorCode::InvalidPositionAccounts,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RequestUnstake<'info> {
+    // settles the position and takes it out of the pool, the principal stays in its
+    // vault until withdraw_sol / withdraw_spl after the unbonding period
+    pub fn request_unstake(&mut self, reward_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        if self.stake_account.kind == StakeKind::Sol {
+            self.config.require_not_paused(PAUSE_UNSTAKE_SOL, ErrorCode::UnstakeSolPaused)?;
+        } else {
+            self.config.require_not_paused(PAUSE_UNSTAKE_SPL, ErrorCode::UnstakeSplPaused)?;
+        }
+        require!(self.stake_account.withdrawable_at == 0, ErrorCode::AlreadyUnbonding);
+
+        let current = Clock::get()?.unix_timestamp;
+        require!(current >= self.stake_account.unlock_at, ErrorCode::FreezePeriodeNotPassed);
+
+        let payout = RewardPayout {
+            reward_mint: &self.reward_mint,
+            reward_treasury: self.reward_treasury.as_ref(),
+            to: &self.user_reward_ata,
+            token_program: &self.token_program,
+        };
+        settle_position(&mut self.config, &mut self.stake_account, &mut self.user_account, payout, reward_accounts, current, Shortfall::Forfeit)?;
+
+        self.config.total_staked_weight = self.config.total_staked_weight.checked_sub(self.stake_account.weight).ok_or(ErrorCode::Overflow)?;
+        self.stake_account.weight = 0;
+        self.stake_account.reward_debt = 0;
+        self.stake_account.extra_reward_debts.clear();
+        self.stake_account.withdrawable_at = current.checked_add(i64::from(self.config.unbonding_period)).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
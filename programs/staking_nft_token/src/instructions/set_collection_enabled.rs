@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::{AllowedCollection, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct SetCollectionEnabled<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"collection", config.key().as_ref(), allowed_collection.collection_mint.as_ref()],
+        bump = allowed_collection.bump
+    )]
+    pub allowed_collection: Account<'info, AllowedCollection>,
+}
+
+impl<'info> SetCollectionEnabled<'info> {
+    // disabling only blocks new stakes, NFTs already staked can still be unstaked
+    pub fn set_collection_enabled(&mut self, enabled: bool) -> Result<()> {
+        self.allowed_collection.enabled = enabled;
+        Ok(())
+    }
+}
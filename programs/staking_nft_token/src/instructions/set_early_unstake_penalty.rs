@@ -0,0 +1,48 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+
+use crate::{constants::BPS_DENOMINATOR, error::ErrorCode, state::StakeConfigAccount};
+
+#[derive(Accounts)]
+pub struct SetEarlyUnstakePenalty<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"penalty", config.key().as_ref()],
+        bump
+    )]
+    pub penalty_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetEarlyUnstakePenalty<'info> {
+    pub fn set_early_unstake_penalty(&mut self, penalty_bps: u16) -> Result<()> {
+        require!(u64::from(penalty_bps) <= BPS_DENOMINATOR, ErrorCode::InvalidPenalty);
+
+        // fund the vault up to rent exemption so penalties of any size can be transferred into it
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        let missing = rent_exempt.saturating_sub(self.penalty_vault.lamports());
+        if missing > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.admin.to_account_info(),
+                to: self.penalty_vault.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            transfer(cpi_ctx, missing)?;
+        }
+
+        self.config.early_unstake_penalty_bps = penalty_bps;
+        Ok(())
+    }
+}
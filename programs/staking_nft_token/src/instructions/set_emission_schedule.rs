@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::{EmissionSchedule, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct SetEmissionSchedule<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+}
+
+impl<'info> SetEmissionSchedule<'info> {
+    pub fn set_emission_schedule(&mut self, schedule: EmissionSchedule) -> Result<()> {
+        schedule.validate()?;
+
+        // settle accrual under the old schedule first
+        let now = Clock::get()?.unix_timestamp;
+        self.config.update_indexes(now)?;
+        self.config.update_pool(now)?;
+        self.config.schedule = schedule;
+        Ok(())
+    }
+}
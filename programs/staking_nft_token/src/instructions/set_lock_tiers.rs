@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::{validate_lock_tiers, LockTier, LockTierParams, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct SetLockTiers<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+}
+
+impl<'info> SetLockTiers<'info> {
+    // open positions keep the unlock time and multiplier they were staked with
+    pub fn set_lock_tiers(&mut self, lock_tiers: [LockTierParams; LockTier::COUNT]) -> Result<()> {
+        validate_lock_tiers(&lock_tiers)?;
+        self.config.lock_tiers = lock_tiers;
+        Ok(())
+    }
+}
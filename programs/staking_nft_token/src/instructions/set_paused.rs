@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::stake_config_account::StakeConfigAccount};
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+}
+
+impl<'info> SetPaused<'info> {
+    pub fn set_paused(&mut self, paused: u8) -> Result<()> {
+        // with PAUSE_CLAIM every bit is a flag of PAUSE_ALL_FLAGS, so any value is valid
+        self.config.paused = paused;
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::{AllowedSplMint, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct SetSplMintEnabled<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"spl_mint", config.key().as_ref(), allowed_spl_mint.mint.as_ref()],
+        bump = allowed_spl_mint.bump
+    )]
+    pub allowed_spl_mint: Account<'info, AllowedSplMint>,
+}
+
+impl<'info> SetSplMintEnabled<'info> {
+    // disabling only blocks new stakes, tokens already staked can still be unstaked
+    pub fn set_spl_mint_enabled(&mut self, enabled: bool) -> Result<()> {
+        self.allowed_spl_mint.enabled = enabled;
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::StakeConfigAccount};
+
+#[derive(Accounts)]
+pub struct SetUnbondingPeriod<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+}
+
+impl<'info> SetUnbondingPeriod<'info> {
+    // positions already unbonding keep the withdraw time they were given
+    pub fn set_unbonding_period(&mut self, unbonding_period: u32) -> Result<()> {
+        self.config.unbonding_period = unbonding_period;
+        Ok(())
+    }
+}
@@ -6,11 +6,11 @@ use anchor_spl::{
         },
         MasterEditionAccount, Metadata, MetadataAccount,
     },
-    token::{approve, mint_to, Approve, Mint, MintTo, Token, TokenAccount},
+    token::{approve, Approve, Mint, Token, TokenAccount},
 };
 
 
-use crate::{error::ErrorCode, StakeAccount, StakeConfigAccount, UserAccount};
+use crate::{constants::PAUSE_STAKE_NFT, error::ErrorCode, AllowedCollection, LockTier, StakeKind, StakeAccount, StakeConfigAccount, UserAccount};
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
@@ -24,25 +24,20 @@ pub struct StakeNFT<'info> {
 
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = user
-    )]
-    pub mint_ata: Account<'info, TokenAccount>,
-
-    #[account(
-        mut,
-        seeds = [b"rewards", config.key().as_ref()],
-        bump = config.reward_bump,
-        mint::authority = config,
+        has_one = config,
+        has_one = collection_mint,
+        constraint = allowed_collection.enabled @ ErrorCode::CollectionDisabled,
+        seeds = [b"collection", config.key().as_ref(), collection_mint.key().as_ref()],
+        bump = allowed_collection.bump
     )]
-    pub reward_mint: Account<'info, Mint>,
+    pub allowed_collection: Account<'info, AllowedCollection>,
 
     #[account(
         mut,
-        associated_token::mint = reward_mint,
-        associated_token::authority = user,
+        associated_token::mint = mint,
+        associated_token::authority = user
     )]
-    pub user_reward_ata: Account<'info, TokenAccount>,
+    pub mint_ata: Account<'info, TokenAccount>,
 
     #[account(
         seeds = [
@@ -53,7 +48,7 @@ pub struct StakeNFT<'info> {
         bump,
         seeds::program = metadata_program.key(),
         constraint = metadata.collection.as_ref().unwrap().key.as_ref() == collection_mint.key().as_ref(),
-        constraint = metadata.collection.as_ref().unwrap().verified == true
+        constraint = metadata.collection.as_ref().unwrap().verified
 
     )]
     pub metadata: Account<'info, MetadataAccount>,
@@ -80,7 +75,8 @@ pub struct StakeNFT<'info> {
     pub stake_account: Account<'info, StakeAccount>,
 
     #[account(
-        seeds = [b"config"],
+        mut,
+        seeds = [b"config", config.pool_seed().as_ref()],
         bump = config.bump,
     )]
     pub config: Account<'info, StakeConfigAccount>,
@@ -98,7 +94,9 @@ pub struct StakeNFT<'info> {
 }
 
 impl<'info> StakeNFT<'info> {
-    pub fn stake_nft(&mut self, seed:u64, bumps: &StakeNFTBumps) -> Result<()> {
+    pub fn stake_nft(&mut self, seed:u64, lock_tier: LockTier, bumps: &StakeNFTBumps) -> Result<()> {
+        self.config.require_not_paused(PAUSE_STAKE_NFT, ErrorCode::StakeNftPaused)?;
+
         let cpi_program = self.token_program.to_account_info();
         let cpi_account = Approve {
             to: self.mint_ata.to_account_info(),
@@ -138,45 +136,43 @@ impl<'info> StakeNFT<'info> {
         )
         .invoke_signed(signer_seeds)?;
 
+        let now = Clock::get()?.unix_timestamp;
+
+        self.config.update_indexes(now)?;
+        self.allowed_collection.update_index(&self.config)?;
+
+        let tier = self.config.lock_tier(lock_tier);
         self.stake_account.set_inner(StakeAccount {
+            version: StakeAccount::VERSION,
             owner: self.user.key(),
+            config: self.config.key(),
+            kind: StakeKind::Nft,
             mint: self.mint.key(),
-            staked_at: Clock::get()?.unix_timestamp,
+            collection: Some(self.collection_mint.key()),
+            staked_at: now,
+            last_claimed_at: now,
+            unlock_at: tier.unlock_at(now, self.allowed_collection.min_freeze_period(&self.config))?,
+            multiplier_bps: tier.multiplier_bps,
+            withdrawable_at: 0,
+            weight: 1,
+            reward_debt: 0,
+            amount: 1,
+            extra_reward_debts: Vec::new(),
             bump: bumps.stake_account,
             vault_bump: 0,
             seed,
+            legacy_seeds: false,
+            lock_duration: tier.lock_duration(self.allowed_collection.min_freeze_period(&self.config)),
+            reserved: [0; 27],
         });
+        self.stake_account.checkpoint_rewards(self.allowed_collection.reward_index)?;
 
-        let points_u64 = u64::try_from(self.config.points_per_nft_stake).or(Err(ErrorCode::Overflow))?;
-
-        let reward_amount = points_u64.checked_mul(1_000_000u64).unwrap();
-
-        self.reward_user(reward_amount)?;
-
-        self.user_account.points = self.user_account.points.checked_add(reward_amount).ok_or(ErrorCode::Overflow)?;
         self.user_account.nft_staked_amount = self.user_account.nft_staked_amount.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        self.allowed_collection.staked_count = self.allowed_collection.staked_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        let weight = u64::from(self.stake_account.multiplier_bps);
+        self.allowed_collection.staked_weight = self.allowed_collection.staked_weight.checked_add(weight).ok_or(ErrorCode::Overflow)?;
+        self.config.add_nft_weight(self.allowed_collection.points_per_nft_stake, weight)?;
 
         Ok(())
     }
-
-    pub fn reward_user(&mut self, amount: u64) -> Result<()> {
-        let cpi_program = self.token_program.to_account_info();
-
-        let cpi_accounts = MintTo {
-            mint: self.reward_mint.to_account_info(),
-            to: self.user_reward_ata.to_account_info(),
-            authority: self.config.to_account_info()
-        };
-
-        let seeds = &[
-            &b"config"[..],
-            &[self.config.bump]
-        ];
-
-        let signer_seeds = &[&seeds[..]];
-
-        let ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-
-        mint_to(ctx, amount)
-    }
-}
\ No newline at end of file
+}
@@ -1,8 +1,9 @@
 use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
-use anchor_spl::token::{mint_to, spl_token::{instruction, native_mint}, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token::spl_token::native_mint;
 
-use crate::state::{StakeConfigAccount, UserAccount, StakeAccount};
+use crate::state::{apply_multiplier, LockTier, StakeConfigAccount, UserAccount, StakeAccount, StakeKind};
 use crate::error::ErrorCode;
+use crate::constants::PAUSE_STAKE_SOL;
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
@@ -12,15 +13,7 @@ pub struct StakeSol<'info> {
 
     #[account(
         mut,
-        seeds = [b"rewards", config.key().as_ref()],
-        bump = config.reward_bump,
-        mint::authority = config
-    )]
-    pub reward_mint: Account<'info, Mint>,
-
-    #[account(
-        mut,
-        seeds = [b"config"],
+        seeds = [b"config", config.pool_seed().as_ref()],
         bump = config.bump
     )]
     pub config: Account<'info, StakeConfigAccount>,
@@ -41,13 +34,6 @@ pub struct StakeSol<'info> {
     )]
     pub user_account: Account<'info, UserAccount>,
 
-    #[account(
-        mut,
-        associated_token::mint = reward_mint,
-        associated_token::authority = user
-    )]
-    pub user_reward_ata: Account<'info, TokenAccount>,
-
     #[account(
         mut,
         seeds = [b"vault", stake_account.key().as_ref()],
@@ -56,11 +42,13 @@ pub struct StakeSol<'info> {
     pub vault: SystemAccount<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
 }
 
 impl<'info> StakeSol<'info> {
-    pub fn stake_sol(&mut self,seed: u64,amount: u64, bumps: &StakeSolBumps) -> Result<()> {
+    pub fn stake_sol(&mut self,seed: u64,amount: u64, lock_tier: LockTier, bumps: &StakeSolBumps) -> Result<()> {
+        self.config.require_not_paused(PAUSE_STAKE_SOL, ErrorCode::StakeSolPaused)?;
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+
         let cpi_program = self.system_program.to_account_info();
         let cpi_accounts = Transfer {
             from: self.user.to_account_info(),
@@ -69,35 +57,40 @@ impl<'info> StakeSol<'info> {
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         transfer(cpi_ctx, amount)?;
 
-        let points_u64 = u64::try_from(self.config.points_per_sol_stake).or(Err(ErrorCode::Overflow))?;
-        let reward_amount = points_u64.checked_mul(amount).ok_or(ErrorCode::Overflow)?;
-
-        self.user_account.points = self.user_account.points.checked_add(reward_amount).ok_or(ErrorCode::Overflow)?;
         self.user_account.sol_staked_amount = self.user_account.sol_staked_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
 
-        self.reward_user(reward_amount)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        self.config.update_pool(now)?;
+        let tier = self.config.lock_tier(lock_tier);
+        let weight = apply_multiplier(self.config.sol_stake_weight(amount)?, tier.multiplier_bps)?;
+        self.config.total_staked_weight = self.config.total_staked_weight.checked_add(weight).ok_or(ErrorCode::Overflow)?;
 
         self.stake_account.set_inner(StakeAccount {
+            version: StakeAccount::VERSION,
             owner: self.user.key(),
+            config: self.config.key(),
+            kind: StakeKind::Sol,
             mint: native_mint::id(),
-            staked_at: Clock::get()?.unix_timestamp,
+            collection: None,
+            staked_at: now,
+            last_claimed_at: now,
+            unlock_at: tier.unlock_at(now, self.config.min_freeze_period)?,
+            multiplier_bps: tier.multiplier_bps,
+            withdrawable_at: 0,
+            weight,
+            reward_debt: 0,
+            amount,
+            extra_reward_debts: Vec::new(),
             bump: bumps.stake_account,
             vault_bump: bumps.vault,
-            seed
+            seed,
+            legacy_seeds: false,
+            lock_duration: tier.lock_duration(self.config.min_freeze_period),
+            reserved: [0; 27],
         });
+        self.stake_account.checkpoint_rewards(self.config.acc_reward_per_share)?;
+        self.stake_account.checkpoint_extra_rewards(&self.config.extra_rewards)?;
         Ok(())
     }
-
-    pub fn reward_user(&mut self, amount: u64) -> Result<()> {
-        let cpi_program = self.token_program.to_account_info();
-        let cpi_accounts = MintTo {
-            mint: self.reward_mint.to_account_info(),
-            to: self.user_reward_ata.to_account_info(),
-            authority: self.config.to_account_info(),
-        };
-        let seeds = &[&b"config"[..], &[self.config.bump]];
-        let signer_seeds = &[&seeds[..]];
-        let ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        mint_to(ctx, amount)
-    }
-}
\ No newline at end of file
+}
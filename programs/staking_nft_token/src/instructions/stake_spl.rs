@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{mint_to, transfer_checked, Mint, MintTo, Token, TokenAccount, TransferChecked},
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
 };
 
-use crate::state::{StakeConfigAccount, UserAccount, StakeAccount};
+use crate::state::{apply_multiplier, AllowedSplMint, LockTier, StakeConfigAccount, UserAccount, StakeAccount, StakeKind};
 use crate::error::ErrorCode;
+use crate::constants::PAUSE_STAKE_SPL;
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
@@ -17,30 +18,25 @@ pub struct StakeSpl<'info> {
 
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = user
+        has_one = config,
+        has_one = mint,
+        constraint = allowed_spl_mint.enabled @ ErrorCode::SplMintDisabled,
+        seeds = [b"spl_mint", config.key().as_ref(), mint.key().as_ref()],
+        bump = allowed_spl_mint.bump
     )]
-    pub mint_ata: Account<'info, TokenAccount>,
+    pub allowed_spl_mint: Account<'info, AllowedSplMint>,
 
     #[account(
         mut,
-        seeds = [b"rewards", config.key().as_ref()],
-        bump = config.reward_bump,
-        mint::authority = config,
-    )]
-    pub reward_mint: Account<'info, Mint>,
-
-    #[account(
-        mut,
-        associated_token::mint = reward_mint,
-        associated_token::authority = user,
+        associated_token::mint = mint,
+        associated_token::authority = user
     )]
-    pub user_reward_ata: Account<'info, TokenAccount>,
+    pub mint_ata: Account<'info, TokenAccount>,
 
     #[account(
         init,
         payer = user,
-        seeds = [b"stake", config.key().as_ref(), user.key().as_ref(), mint.key().as_ref()],
+        seeds = [b"stake", config.key().as_ref(), user.key().as_ref(), mint.key().as_ref(), seed.to_le_bytes().as_ref()],
         bump,
         space = 8 + StakeAccount::INIT_SPACE
     )]
@@ -48,7 +44,7 @@ pub struct StakeSpl<'info> {
     
     #[account(
         mut,
-        seeds = [b"config"],
+        seeds = [b"config", config.pool_seed().as_ref()],
         bump = config.bump,
     )]
     pub config: Account<'info, StakeConfigAccount>,
@@ -74,7 +70,10 @@ pub struct StakeSpl<'info> {
 } 
 
 impl<'info> StakeSpl<'info> {
-    pub fn stake_spl(&mut self,seed: u64,amount: u64, bumps: &StakeSplBumps) -> Result<()> {
+    pub fn stake_spl(&mut self,seed: u64,amount: u64, lock_tier: LockTier, bumps: &StakeSplBumps) -> Result<()> {
+        self.config.require_not_paused(PAUSE_STAKE_SPL, ErrorCode::StakeSplPaused)?;
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+
         let cpi_program = self.token_program.to_account_info();
         let cpi_accounts = TransferChecked {
             from: self.mint_ata.to_account_info(),
@@ -85,35 +84,41 @@ impl<'info> StakeSpl<'info> {
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
 
-        let points_u64 = u64::try_from(self.config.points_per_spl_stake).or(Err(ErrorCode::Overflow))?;
-        let reward_amount = points_u64.checked_mul(amount).ok_or(ErrorCode::Overflow)?;
-
-        self.user_account.points = self.user_account.points.checked_add(reward_amount).ok_or(ErrorCode::Overflow)?;
         self.user_account.spl_staked_amount = self.user_account.spl_staked_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        self.allowed_spl_mint.staked_amount = self.allowed_spl_mint.staked_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        let now = Clock::get()?.unix_timestamp;
 
-        self.reward_user(reward_amount)?;
+        self.config.update_pool(now)?;
+        let tier = self.config.lock_tier(lock_tier);
+        let weight = apply_multiplier(self.allowed_spl_mint.stake_weight(&self.config, amount)?, tier.multiplier_bps)?;
+        self.config.total_staked_weight = self.config.total_staked_weight.checked_add(weight).ok_or(ErrorCode::Overflow)?;
 
         self.stake_account.set_inner(StakeAccount {
+            version: StakeAccount::VERSION,
             owner: self.user.key(),
+            config: self.config.key(),
+            kind: StakeKind::Spl,
             mint: self.mint.key(),
-            staked_at: Clock::get()?.unix_timestamp,
+            collection: None,
+            staked_at: now,
+            last_claimed_at: now,
+            unlock_at: tier.unlock_at(now, self.config.min_freeze_period)?,
+            multiplier_bps: tier.multiplier_bps,
+            withdrawable_at: 0,
+            weight,
+            reward_debt: 0,
+            amount,
+            extra_reward_debts: Vec::new(),
             bump: bumps.stake_account,
             vault_bump: 0,
-            seed    
+            seed,
+            legacy_seeds: false,
+            lock_duration: tier.lock_duration(self.config.min_freeze_period),
+            reserved: [0; 27],
         });
+        self.stake_account.checkpoint_rewards(self.config.acc_reward_per_share)?;
+        self.stake_account.checkpoint_extra_rewards(&self.config.extra_rewards)?;
         Ok(())
     }
-
-    pub fn reward_user(&mut self, amount: u64) -> Result<()> {
-        let cpi_program = self.token_program.to_account_info();
-        let cpi_accounts = MintTo {
-            mint: self.reward_mint.to_account_info(),
-            to: self.user_reward_ata.to_account_info(),
-            authority: self.config.to_account_info(),
-        };
-        let seeds = &[&b"config"[..], &[self.config.bump]];
-        let signer_seeds = &[&seeds[..]];
-        let ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        mint_to(ctx, amount)
-    }
 }
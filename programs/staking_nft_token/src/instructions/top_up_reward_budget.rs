@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::{RewardMode, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct TopUpRewardBudget<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+}
+
+impl<'info> TopUpRewardBudget<'info> {
+    pub fn top_up_reward_budget(&mut self, amount: u64) -> Result<()> {
+        // treasury budgets are backed by deposits and only grow through fund_rewards
+        require!(self.config.reward_mode == RewardMode::Mint, ErrorCode::InvalidRewardMode);
+
+        self.config.top_up_reward_budget(amount)
+    }
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::{RewardMode, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct TopUpRewardTokenBudget<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+}
+
+impl<'info> TopUpRewardTokenBudget<'info> {
+    pub fn top_up_reward_token_budget(&mut self, index: u8, amount: u64) -> Result<()> {
+        let extra = self.config.extra_rewards.get_mut(usize::from(index)).ok_or(ErrorCode::InvalidRewardToken)?;
+        // treasury budgets are backed by deposits and only grow through fund_reward_token
+        require!(extra.mode == RewardMode::Mint, ErrorCode::InvalidRewardMode);
+
+        extra.top_up_reward_budget(amount)
+    }
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{metadata::{mpl_token_metadata::instructions::{ ThawDelegatedAccountCpi, ThawDelegatedAccountCpiAccounts}, MasterEditionAccount, Metadata, MetadataAccount}, token::{ revoke, Mint, Revoke, Token, TokenAccount}};
 
-use crate::{error::ErrorCode, StakeAccount, StakeConfigAccount, UserAccount};
+use crate::{constants::PAUSE_UNSTAKE_NFT, error::ErrorCode, utils::pay_rewards, AllowedCollection, StakeAccount, StakeKind, StakeConfigAccount, UserAccount};
 
 #[derive(Accounts)]
 pub struct UnStakeNFT<'info> {
@@ -12,6 +12,15 @@ pub struct UnStakeNFT<'info> {
 
     pub collection_mint: Account<'info, Mint>,
 
+    #[account(
+        mut,
+        has_one = config,
+        has_one = collection_mint,
+        seeds = [b"collection", config.key().as_ref(), collection_mint.key().as_ref()],
+        bump = allowed_collection.bump
+    )]
+    pub allowed_collection: Account<'info, AllowedCollection>,
+
     #[account(
         mut,
         associated_token::mint = mint,
@@ -21,9 +30,7 @@ pub struct UnStakeNFT<'info> {
 
     #[account(
         mut,
-        seeds = [b"rewards", config.key().as_ref()],
-        bump = config.reward_bump,
-        mint::authority = config,
+        address = config.reward_mint,
     )]
     pub reward_mint: Account<'info, Mint>,
 
@@ -34,6 +41,14 @@ pub struct UnStakeNFT<'info> {
     )]
     pub user_reward_ata: Account<'info, TokenAccount>,
 
+    // only used by pools paying rewards out of a treasury
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+    )]
+    pub reward_treasury: Option<Account<'info, TokenAccount>>,
+
     #[account(
         seeds = [
             b"metadata",
@@ -43,7 +58,7 @@ pub struct UnStakeNFT<'info> {
         bump,
         seeds::program = metadata_program.key(),
         constraint = metadata.collection.as_ref().unwrap().key.as_ref() == collection_mint.key().as_ref(),
-        constraint = metadata.collection.as_ref().unwrap().verified == true
+        constraint = metadata.collection.as_ref().unwrap().verified
 
     )]
     pub metadata: Account<'info, MetadataAccount>,
@@ -64,6 +79,8 @@ pub struct UnStakeNFT<'info> {
         mut,
         close = user,
         has_one = mint,
+        has_one = config,
+        constraint = stake_account.kind == StakeKind::Nft @ ErrorCode::InvalidPositionAccounts,
         seeds = [b"stake", config.key().as_ref(), mint.key().as_ref(), stake_account.seed.to_le_bytes().as_ref()],
         bump = stake_account.bump,
 
@@ -71,7 +88,8 @@ pub struct UnStakeNFT<'info> {
     pub stake_account: Account<'info, StakeAccount>,
 
     #[account(
-        seeds = [b"config"],
+        mut,
+        seeds = [b"config", config.pool_seed().as_ref()],
         bump = config.bump,
     )]
     pub config: Account<'info, StakeConfigAccount>,
@@ -90,11 +108,30 @@ pub struct UnStakeNFT<'info> {
 
 impl<'info> UnStakeNFT<'info> {
     pub fn unstake_nft(&mut self) -> Result<()> {
+        self.config.require_not_paused(PAUSE_UNSTAKE_NFT, ErrorCode::UnstakeNftPaused)?;
 
-        let staked_at = self.stake_account.staked_at;
         let current = Clock::get()?.unix_timestamp;
-
-        require!(current.checked_sub(staked_at).unwrap() >= self.config.min_freeze_period as i64, ErrorCode::FreezePeriodeNotPassed);
+        require!(current >= self.stake_account.unlock_at, ErrorCode::FreezePeriodeNotPassed);
+
+        self.config.update_indexes(current)?;
+        self.allowed_collection.update_index(&self.config)?;
+        let reward_amount = self.stake_account.pending_rewards(self.allowed_collection.reward_index)?;
+        let reward_amount = self.stake_account.apply_multiplier(reward_amount)?;
+        self.config.release_accrued_rewards(reward_amount);
+        // an empty budget must not lock principal in, whatever it cannot cover is forfeited
+        let reward_amount = reward_amount.min(self.config.reward_budget);
+        if reward_amount > 0 {
+            pay_rewards(
+                &mut self.config,
+                &self.reward_mint,
+                self.reward_treasury.as_ref(),
+                &self.user_reward_ata,
+                &self.token_program,
+                reward_amount,
+            )?;
+            self.user_account.points = self.user_account.points.checked_add(reward_amount).ok_or(ErrorCode::Overflow)?;
+        }
+        self.stake_account.last_claimed_at = current;
 
         let seeds = &[
             b"stake",
@@ -137,8 +174,11 @@ impl<'info> UnStakeNFT<'info> {
         revoke(cpi_ctx)?;
 
         self.user_account.nft_staked_amount = self.user_account.nft_staked_amount.checked_sub(1).ok_or(ErrorCode::Overflow)?;
+        self.allowed_collection.staked_count = self.allowed_collection.staked_count.checked_sub(1).ok_or(ErrorCode::Overflow)?;
+        let weight = u64::from(self.stake_account.multiplier_bps);
+        self.allowed_collection.staked_weight = self.allowed_collection.staked_weight.checked_sub(weight).ok_or(ErrorCode::Overflow)?;
+        self.config.remove_nft_weight(self.allowed_collection.points_per_nft_stake, weight)?;
 
         Ok(())
     }
-
-}
\ No newline at end of file
+}
@@ -1,8 +1,10 @@
 use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::state::{StakeConfigAccount, UserAccount, StakeAccount};
+use crate::state::{StakeConfigAccount, UserAccount, StakeAccount, StakeKind};
 use crate::error::ErrorCode;
+use crate::utils::{settle_position, RewardPayout, Shortfall};
+use crate::constants::PAUSE_UNSTAKE_SOL;
 
 #[derive(Accounts)]
 pub struct UnStakeSoL<'info> {
@@ -13,9 +15,7 @@ pub struct UnStakeSoL<'info> {
 
     #[account(
         mut,
-        seeds = [b"rewards", config.key().as_ref()],
-        bump = config.reward_bump,
-        mint::authority = config,
+        address = config.reward_mint,
     )]
     pub reward_mint: Account<'info, Mint>,
 
@@ -26,15 +26,26 @@ pub struct UnStakeSoL<'info> {
     )]
     pub user_reward_ata: Account<'info, TokenAccount>,
 
+    // only used by pools paying rewards out of a treasury
     #[account(
         mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+    )]
+    pub reward_treasury: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = config,
+        constraint = stake_account.kind == StakeKind::Sol @ ErrorCode::InvalidPositionAccounts,
         seeds = [b"stake", config.key().as_ref(), user.key().as_ref(), &stake_account.seed.to_le_bytes()],
         bump = stake_account.bump,
     )]
     pub stake_account: Account<'info, StakeAccount>,
     
     #[account(
-        seeds = [b"config"],
+        mut,
+        seeds = [b"config", config.pool_seed().as_ref()],
         bump = config.bump,
     )]
     pub config: Account<'info, StakeConfigAccount>,
@@ -58,10 +69,32 @@ pub struct UnStakeSoL<'info> {
 } 
 
 impl<'info> UnStakeSoL<'info> {
-    pub fn unstake_sol(&mut self) -> Result<()> {
-        let staked_at = self.stake_account.staked_at;
+    pub fn unstake_sol(&mut self, amount: u64, reward_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        self.config.require_not_paused(PAUSE_UNSTAKE_SOL, ErrorCode::UnstakeSolPaused)?;
+        require!(self.config.unbonding_period == 0, ErrorCode::UnbondingRequired);
+
         let current = Clock::get()?.unix_timestamp;
-        require!(current.checked_sub(staked_at).unwrap() >= self.config.min_freeze_period as i64, ErrorCode::FreezePeriodeNotPassed);
+        require!(current >= self.stake_account.unlock_at, ErrorCode::FreezePeriodeNotPassed);
+
+        require!(amount > 0 && amount <= self.stake_account.amount, ErrorCode::InvalidUnstakeAmount);
+        let remaining = self.stake_account.amount - amount;
+        // the vault is a plain system account, it has to stay rent exempt until it is emptied
+        require!(remaining == 0 || remaining >= Rent::get()?.minimum_balance(0), ErrorCode::InvalidUnstakeAmount);
+
+        let payout = RewardPayout {
+            reward_mint: &self.reward_mint,
+            reward_treasury: self.reward_treasury.as_ref(),
+            to: &self.user_reward_ata,
+            token_program: &self.token_program,
+        };
+        settle_position(&mut self.config, &mut self.stake_account, &mut self.user_account, payout, reward_accounts, current, Shortfall::Forfeit)?;
+
+        let removed_weight = self.stake_account.weight_share(amount)?;
+        self.config.total_staked_weight = self.config.total_staked_weight.checked_sub(removed_weight).ok_or(ErrorCode::Overflow)?;
+        self.stake_account.weight -= removed_weight;
+        self.stake_account.amount = remaining;
+        self.stake_account.checkpoint_rewards(self.config.acc_reward_per_share)?;
+        self.stake_account.checkpoint_extra_rewards(&self.config.extra_rewards)?;
 
         let seeds = &[
             b"vault",
@@ -70,15 +103,22 @@ impl<'info> UnStakeSoL<'info> {
         ];
         let signer_seeds = &[&seeds[..]];
 
+        // emptying the position also sweeps anything sent to the vault on top of the principal
+        let payout = if remaining == 0 { self.vault.lamports() } else { amount };
+
         let cpi_program = self.system_program.to_account_info();
         let cpi_accounts = Transfer {
             from: self.vault.to_account_info(),
             to: self.user.to_account_info(),
         };
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        transfer(cpi_ctx, self.vault.lamports())?;
+        transfer(cpi_ctx, payout)?;
+
+        self.user_account.sol_staked_amount = self.user_account.sol_staked_amount.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
 
-        self.user_account.sol_staked_amount = self.user_account.sol_staked_amount.checked_sub(self.vault.lamports()).ok_or(ErrorCode::Overflow)?;
+        if remaining == 0 {
+            self.stake_account.close(self.user.to_account_info())?;
+        }
         Ok(())
     }
-}
\ No newline at end of file
+}
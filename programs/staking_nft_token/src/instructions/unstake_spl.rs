@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{transfer_checked, close_account, Mint, Token, TokenAccount, TransferChecked, CloseAccount};
 
-use crate::state::{StakeConfigAccount, UserAccount, StakeAccount};
+use crate::state::{AllowedSplMint, StakeConfigAccount, UserAccount, StakeAccount, StakeKind};
 use crate::error::ErrorCode;
+use crate::utils::{settle_position, RewardPayout, Shortfall};
+use crate::constants::PAUSE_UNSTAKE_SPL;
 
 #[derive(Accounts)]
 pub struct UnStakeSpL<'info> {
@@ -11,6 +13,15 @@ pub struct UnStakeSpL<'info> {
 
     pub mint: Account<'info, Mint>,
 
+    #[account(
+        mut,
+        has_one = config,
+        has_one = mint,
+        seeds = [b"spl_mint", config.key().as_ref(), mint.key().as_ref()],
+        bump = allowed_spl_mint.bump
+    )]
+    pub allowed_spl_mint: Account<'info, AllowedSplMint>,
+
     #[account(
         mut,
         associated_token::mint = mint,
@@ -20,9 +31,7 @@ pub struct UnStakeSpL<'info> {
 
     #[account(
         mut,
-        seeds = [b"rewards", config.key().as_ref()],
-        bump = config.reward_bump,
-        mint::authority = config,
+        address = config.reward_mint,
     )]
     pub reward_mint: Account<'info, Mint>,
 
@@ -33,22 +42,32 @@ pub struct UnStakeSpL<'info> {
     )]
     pub user_reward_ata: Account<'info, TokenAccount>,
 
+    // only used by pools paying rewards out of a treasury
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+    )]
+    pub reward_treasury: Option<Account<'info, TokenAccount>>,
+
     #[account(
         mut,
-        seeds = [b"stake", config.key().as_ref(), user.key().as_ref(), mint.key().as_ref()],
+        has_one = config,
+        constraint = stake_account.kind == StakeKind::Spl @ ErrorCode::InvalidPositionAccounts,
+        seeds = [b"stake", config.key().as_ref(), user.key().as_ref(), mint.key().as_ref(), stake_account.seed_bytes().as_ref()],
         bump = stake_account.bump,
     )]
     pub stake_account: Account<'info, StakeAccount>,
     
     #[account(
-        seeds = [b"config"],
+        mut,
+        seeds = [b"config", config.pool_seed().as_ref()],
         bump = config.bump,
     )]
     pub config: Account<'info, StakeConfigAccount>,
 
     #[account(
         mut,
-        close = user,
         associated_token::mint = mint,
         associated_token::authority = stake_account,
     )]
@@ -66,20 +85,44 @@ pub struct UnStakeSpL<'info> {
 } 
 
 impl<'info> UnStakeSpL<'info> {
-    pub fn unstake_spl(&mut self) -> Result<()> {
-        let staked_at = self.stake_account.staked_at;
+    pub fn unstake_spl(&mut self, amount: u64, reward_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        self.config.require_not_paused(PAUSE_UNSTAKE_SPL, ErrorCode::UnstakeSplPaused)?;
+        require!(self.config.unbonding_period == 0, ErrorCode::UnbondingRequired);
+
         let current = Clock::get()?.unix_timestamp;
-        require!(current.checked_sub(staked_at).unwrap() >= self.config.min_freeze_period as i64, ErrorCode::FreezePeriodeNotPassed);
+        require!(current >= self.stake_account.unlock_at, ErrorCode::FreezePeriodeNotPassed);
+
+        require!(amount > 0 && amount <= self.stake_account.amount, ErrorCode::InvalidUnstakeAmount);
+        let remaining = self.stake_account.amount - amount;
+
+        let payout = RewardPayout {
+            reward_mint: &self.reward_mint,
+            reward_treasury: self.reward_treasury.as_ref(),
+            to: &self.user_reward_ata,
+            token_program: &self.token_program,
+        };
+        settle_position(&mut self.config, &mut self.stake_account, &mut self.user_account, payout, reward_accounts, current, Shortfall::Forfeit)?;
+
+        let removed_weight = self.stake_account.weight_share(amount)?;
+        self.config.total_staked_weight = self.config.total_staked_weight.checked_sub(removed_weight).ok_or(ErrorCode::Overflow)?;
+        self.stake_account.weight -= removed_weight;
+        self.stake_account.amount = remaining;
+        self.stake_account.checkpoint_rewards(self.config.acc_reward_per_share)?;
+        self.stake_account.checkpoint_extra_rewards(&self.config.extra_rewards)?;
 
         let seeds = &[
             b"stake",
             self.config.to_account_info().key.as_ref(),
             self.user.to_account_info().key.as_ref(),
             self.mint.to_account_info().key.as_ref(),
+            &self.stake_account.seed_bytes()[..],
             &[self.stake_account.bump],
         ];
         let signer_seeds = &[&seeds[..]];
 
+        // emptying the position also sweeps anything sent to the vault on top of the principal
+        let payout = if remaining == 0 { self.vault_ata.amount } else { amount };
+
         let cpi_program = self.token_program.to_account_info();
         let cpi_accounts = TransferChecked {
             from: self.vault_ata.to_account_info(),
@@ -88,17 +131,21 @@ impl<'info> UnStakeSpL<'info> {
             authority: self.stake_account.to_account_info(),
         };
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        transfer_checked(cpi_ctx, self.vault_ata.amount, self.mint.decimals)?;
+        transfer_checked(cpi_ctx, payout, self.mint.decimals)?;
 
-        self.user_account.spl_staked_amount = self.user_account.spl_staked_amount.checked_add(self.vault_ata.amount).ok_or(ErrorCode::Overflow)?;
+        self.user_account.spl_staked_amount = self.user_account.spl_staked_amount.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+        self.allowed_spl_mint.staked_amount = self.allowed_spl_mint.staked_amount.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
 
-        let close_accounts = CloseAccount {
-            account: self.vault_ata.to_account_info(),
-            destination: self.user.to_account_info(),
-            authority: self.stake_account.to_account_info(),
-        };
-        let close_cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), close_accounts, signer_seeds);
-        close_account(close_cpi_ctx)?;
+        if remaining == 0 {
+            let close_accounts = CloseAccount {
+                account: self.vault_ata.to_account_info(),
+                destination: self.user.to_account_info(),
+                authority: self.stake_account.to_account_info(),
+            };
+            let close_cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), close_accounts, signer_seeds);
+            close_account(close_cpi_ctx)?;
+            self.stake_account.close(self.user.to_account_info())?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::{AllowedCollection, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct UpdateCollection<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"collection", config.key().as_ref(), allowed_collection.collection_mint.as_ref()],
+        bump = allowed_collection.bump
+    )]
+    pub allowed_collection: Account<'info, AllowedCollection>,
+}
+
+impl<'info> UpdateCollection<'info> {
+    // replaces both overrides, pass None to fall back to the pool values
+    pub fn update_collection(&mut self, points_per_nft_stake: Option<u8>, min_freeze_period: Option<u32>) -> Result<()> {
+        // settle what staked NFTs earned at the old rate before switching
+        self.config.update_indexes(Clock::get()?.unix_timestamp)?;
+        self.allowed_collection.update_index(&self.config)?;
+
+        let staked_weight = self.allowed_collection.staked_weight;
+        self.config.remove_nft_weight(self.allowed_collection.points_per_nft_stake, staked_weight)?;
+        self.config.add_nft_weight(points_per_nft_stake, staked_weight)?;
+
+        self.allowed_collection.points_per_nft_stake = points_per_nft_stake;
+        self.allowed_collection.min_freeze_period = min_freeze_period;
+        Ok(())
+    }
+}
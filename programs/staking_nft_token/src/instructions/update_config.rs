@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::stake_config_account::StakeConfigAccount};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_config(
+        &mut self,
+        points_per_nft_stake: Option<u8>,
+        points_per_spl_stake: Option<u8>,
+        points_per_sol_stake: Option<u8>,
+        min_freeze_period: Option<u32>,
+        emission_per_second: Option<u64>,
+    ) -> Result<()> {
+        // checkpoint accrual at the current rates so the update only applies from now on
+        let now = Clock::get()?.unix_timestamp;
+        self.config.update_indexes(now)?;
+        self.config.update_pool(now)?;
+
+        if let Some(points) = points_per_nft_stake {
+            self.config.points_per_nft_stake = points;
+        }
+        if let Some(points) = points_per_spl_stake {
+            self.config.points_per_spl_stake = points;
+        }
+        if let Some(points) = points_per_sol_stake {
+            self.config.points_per_sol_stake = points;
+        }
+        if let Some(period) = min_freeze_period {
+            self.config.min_freeze_period = period;
+        }
+        if let Some(emission) = emission_per_second {
+            self.config.emission_per_second = emission;
+        }
+        Ok(())
+    }
+}
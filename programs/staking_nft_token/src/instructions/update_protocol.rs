@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::protocol_config_account::ProtocolConfigAccount};
+
+#[derive(Accounts)]
+pub struct UpdateProtocol<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"protocol"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfigAccount>,
+}
+
+impl<'info> UpdateProtocol<'info> {
+    pub fn update_protocol(&mut self, pool_creation_fee: Option<u64>) -> Result<()> {
+        if let Some(fee) = pool_creation_fee {
+            self.protocol_config.pool_creation_fee = fee;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::StakeConfigAccount};
+
+#[derive(Accounts)]
+pub struct UpdateRewardToken<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+}
+
+impl<'info> UpdateRewardToken<'info> {
+    pub fn update_reward_token(&mut self, index: u8, emission_per_second: u64) -> Result<()> {
+        require!(usize::from(index) < self.config.extra_rewards.len(), ErrorCode::InvalidRewardToken);
+
+        // settle accrual at the old rate first
+        self.config.update_pool(Clock::get()?.unix_timestamp)?;
+        self.config.extra_rewards[usize::from(index)].emission_per_second = emission_per_second;
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::{AllowedSplMint, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct UpdateSplMint<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"spl_mint", config.key().as_ref(), allowed_spl_mint.mint.as_ref()],
+        bump = allowed_spl_mint.bump
+    )]
+    pub allowed_spl_mint: Account<'info, AllowedSplMint>,
+}
+
+impl<'info> UpdateSplMint<'info> {
+    pub fn update_spl_mint(&mut self, reward_weight: u16) -> Result<()> {
+        require!(reward_weight > 0, ErrorCode::InvalidRewardWeight);
+
+        self.allowed_spl_mint.reward_weight = reward_weight;
+        Ok(())
+    }
+}
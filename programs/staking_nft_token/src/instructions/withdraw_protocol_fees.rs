@@ -0,0 +1,44 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+
+use crate::{error::ErrorCode, state::protocol_config_account::ProtocolConfigAccount};
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"protocol"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = protocol_config.treasury_bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawProtocolFees<'info> {
+    pub fn withdraw_protocol_fees(&mut self, amount: u64) -> Result<()> {
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        let available = self.treasury.lamports().saturating_sub(rent_exempt);
+        require!(amount <= available, ErrorCode::InsufficientFunds);
+
+        let seeds = &[&b"treasury"[..], &[self.protocol_config.treasury_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: self.treasury.to_account_info(),
+            to: self.admin.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        transfer(cpi_ctx, amount)
+    }
+}
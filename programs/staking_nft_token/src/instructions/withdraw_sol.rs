@@ -0,0 +1,72 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+
+use crate::state::{StakeConfigAccount, UserAccount, StakeAccount, StakeKind};
+use crate::error::ErrorCode;
+use crate::constants::PAUSE_UNSTAKE_SOL;
+
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = config,
+        constraint = stake_account.kind == StakeKind::Sol @ ErrorCode::InvalidPositionAccounts,
+        seeds = [b"stake", config.key().as_ref(), user.key().as_ref(), &stake_account.seed.to_le_bytes()],
+        bump = stake_account.bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", stake_account.key().as_ref()],
+        bump = stake_account.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawSol<'info> {
+    pub fn withdraw_sol(&mut self) -> Result<()> {
+        self.config.require_not_paused(PAUSE_UNSTAKE_SOL, ErrorCode::UnstakeSolPaused)?;
+
+        let withdrawable_at = self.stake_account.withdrawable_at;
+        require!(withdrawable_at != 0, ErrorCode::NotUnbonding);
+        require!(Clock::get()?.unix_timestamp >= withdrawable_at, ErrorCode::CooldownNotPassed);
+
+        let amount = self.stake_account.amount;
+
+        let seeds = &[
+            b"vault",
+            self.stake_account.to_account_info().key.as_ref(),
+            &[self.stake_account.vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        transfer(cpi_ctx, self.vault.lamports())?;
+
+        self.user_account.sol_staked_amount = self.user_account.sol_staked_amount.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+        self.stake_account.close(self.user.to_account_info())
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+
+use crate::{error::ErrorCode, state::StakeConfigAccount};
+
+#[derive(Accounts)]
+pub struct WithdrawSolPenalties<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"penalty", config.key().as_ref()],
+        bump
+    )]
+    pub penalty_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawSolPenalties<'info> {
+    pub fn withdraw_sol_penalties(&mut self, amount: u64, bumps: &WithdrawSolPenaltiesBumps) -> Result<()> {
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        let available = self.penalty_vault.lamports().saturating_sub(rent_exempt);
+        require!(amount <= available, ErrorCode::InsufficientFunds);
+
+        let config_key = self.config.key();
+        let seeds = &[&b"penalty"[..], config_key.as_ref(), &[bumps.penalty_vault]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: self.penalty_vault.to_account_info(),
+            to: self.admin.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        transfer(cpi_ctx, amount)
+    }
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, close_account, Mint, Token, TokenAccount, TransferChecked, CloseAccount};
+
+use crate::state::{AllowedSplMint, StakeConfigAccount, UserAccount, StakeAccount, StakeKind};
+use crate::error::ErrorCode;
+use crate::constants::PAUSE_UNSTAKE_SPL;
+
+#[derive(Accounts)]
+pub struct WithdrawSpl<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = config,
+        has_one = mint,
+        seeds = [b"spl_mint", config.key().as_ref(), mint.key().as_ref()],
+        bump = allowed_spl_mint.bump
+    )]
+    pub allowed_spl_mint: Account<'info, AllowedSplMint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user
+    )]
+    pub mint_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = config,
+        constraint = stake_account.kind == StakeKind::Spl @ ErrorCode::InvalidPositionAccounts,
+        seeds = [b"stake", config.key().as_ref(), user.key().as_ref(), mint.key().as_ref(), stake_account.seed_bytes().as_ref()],
+        bump = stake_account.bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = stake_account,
+    )]
+    pub vault_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawSpl<'info> {
+    pub fn withdraw_spl(&mut self) -> Result<()> {
+        self.config.require_not_paused(PAUSE_UNSTAKE_SPL, ErrorCode::UnstakeSplPaused)?;
+
+        let withdrawable_at = self.stake_account.withdrawable_at;
+        require!(withdrawable_at != 0, ErrorCode::NotUnbonding);
+        require!(Clock::get()?.unix_timestamp >= withdrawable_at, ErrorCode::CooldownNotPassed);
+
+        let amount = self.stake_account.amount;
+
+        let seeds = &[
+            b"stake",
+            self.config.to_account_info().key.as_ref(),
+            self.user.to_account_info().key.as_ref(),
+            self.mint.to_account_info().key.as_ref(),
+            &self.stake_account.seed_bytes()[..],
+            &[self.stake_account.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.vault_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.mint_ata.to_account_info(),
+            authority: self.stake_account.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        transfer_checked(cpi_ctx, self.vault_ata.amount, self.mint.decimals)?;
+
+        self.user_account.spl_staked_amount = self.user_account.spl_staked_amount.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+        self.allowed_spl_mint.staked_amount = self.allowed_spl_mint.staked_amount.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+
+        let close_accounts = CloseAccount {
+            account: self.vault_ata.to_account_info(),
+            destination: self.user.to_account_info(),
+            authority: self.stake_account.to_account_info(),
+        };
+        let close_cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), close_accounts, signer_seeds);
+        close_account(close_cpi_ctx)?;
+        self.stake_account.close(self.user.to_account_info())
+    }
+}
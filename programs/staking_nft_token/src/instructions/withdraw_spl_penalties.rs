@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{error::ErrorCode, state::StakeConfigAccount};
+
+#[derive(Accounts)]
+pub struct WithdrawSplPenalties<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"penalty", config.key().as_ref()],
+        bump
+    )]
+    pub penalty_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = penalty_vault,
+    )]
+    pub penalty_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = admin,
+    )]
+    pub admin_ata: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawSplPenalties<'info> {
+    pub fn withdraw_spl_penalties(&mut self, amount: u64, bumps: &WithdrawSplPenaltiesBumps) -> Result<()> {
+        require!(amount <= self.penalty_ata.amount, ErrorCode::InsufficientFunds);
+
+        let config_key = self.config.key();
+        let seeds = &[&b"penalty"[..], config_key.as_ref(), &[bumps.penalty_vault]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.penalty_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.admin_ata.to_account_info(),
+            authority: self.penalty_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)
+    }
+}
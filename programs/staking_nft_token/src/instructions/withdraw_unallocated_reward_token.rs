@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{error::ErrorCode, state::{RewardMode, StakeConfigAccount}};
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct WithdrawUnallocatedRewardToken<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(
+        constraint = config.extra_rewards.get(usize::from(index)).map(|extra| extra.mint) == Some(reward_mint.key()) @ ErrorCode::InvalidRewardToken,
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = admin,
+    )]
+    pub admin_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+    )]
+    pub reward_treasury: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawUnallocatedRewardToken<'info> {
+    pub fn withdraw_unallocated_reward_token(&mut self, index: u8, amount: u64) -> Result<()> {
+        // rewards already accrued to open positions stay in the treasury for them to claim
+        self.config.update_pool(Clock::get()?.unix_timestamp)?;
+        let extra = &mut self.config.extra_rewards[usize::from(index)];
+        require!(extra.mode == RewardMode::Treasury, ErrorCode::InvalidRewardMode);
+        require!(amount <= extra.unallocated_rewards(), ErrorCode::InsufficientFunds);
+        extra.reward_budget -= amount;
+
+        let pool_seed = self.config.pool_seed();
+        let seeds = &[&b"config"[..], &pool_seed[..], &[self.config.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.reward_treasury.to_account_info(),
+            mint: self.reward_mint.to_account_info(),
+            to: self.admin_ata.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        transfer_checked(cpi_ctx, amount, self.reward_mint.decimals)
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{error::ErrorCode, state::{RewardMode, StakeConfigAccount}};
+
+#[derive(Accounts)]
+pub struct WithdrawUnallocatedRewards<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        seeds = [b"config", config.pool_seed().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfigAccount>,
+
+    #[account(address = config.reward_mint)]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = admin,
+    )]
+    pub admin_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+    )]
+    pub reward_treasury: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawUnallocatedRewards<'info> {
+    pub fn withdraw_unallocated_rewards(&mut self, amount: u64) -> Result<()> {
+        require!(self.config.reward_mode == RewardMode::Treasury, ErrorCode::InvalidRewardMode);
+        // rewards already accrued to open positions stay in the treasury for them to claim
+        let now = Clock::get()?.unix_timestamp;
+        self.config.update_indexes(now)?;
+        self.config.update_pool(now)?;
+        require!(amount <= self.config.unallocated_rewards(), ErrorCode::InsufficientFunds);
+        self.config.reward_budget -= amount;
+
+        let pool_seed = self.config.pool_seed();
+        let seeds = &[&b"config"[..], &pool_seed[..], &[self.config.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.reward_treasury.to_account_info(),
+            mint: self.reward_mint.to_account_info(),
+            to: self.admin_ata.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        transfer_checked(cpi_ctx, amount, self.reward_mint.decimals)
+    }
+}
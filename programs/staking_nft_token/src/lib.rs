@@ -3,6 +3,7 @@ pub mod constants;
 pub mod error;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 
@@ -16,39 +17,228 @@ declare_id!("jgvGJazuaFWvM2185R6C19e1DKqer3kMZz3gJBP1eA9");
 pub mod staking_nft_token {
     use super::*;
 
-    pub fn initialize_config(ctx: Context<InitializeConfig>,points_per_nft_stake: u8,
-        points_per_spl_stake: u8,
-        points_per_sol_stake: u8,
-        min_freeze_period: u32) -> Result<()> {
-        ctx.accounts.initialize_config(points_per_nft_stake, points_per_spl_stake, points_per_sol_stake, min_freeze_period, &ctx.bumps)
+    pub fn initialize_config(ctx: Context<InitializeConfig>, pool_id: u64, params: PoolParams) -> Result<()> {
+        ctx.accounts.initialize_config(pool_id, params, &ctx.bumps)
+    }
+
+    pub fn initialize_protocol(ctx: Context<InitializeProtocol>, pool_creation_fee: u64) -> Result<()> {
+        ctx.accounts.initialize_protocol(pool_creation_fee, &ctx.bumps)
+    }
+
+    pub fn update_protocol(ctx: Context<UpdateProtocol>, pool_creation_fee: Option<u64>) -> Result<()> {
+        ctx.accounts.update_protocol(pool_creation_fee)
+    }
+
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_protocol_fees(amount)
+    }
+
+    pub fn propose_protocol_admin(ctx: Context<ProposeProtocolAdmin>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.propose_protocol_admin(new_admin)
+    }
+
+    pub fn accept_protocol_admin(ctx: Context<AcceptProtocolAdmin>) -> Result<()> {
+        ctx.accounts.accept_protocol_admin()
+    }
+
+    pub fn cancel_protocol_admin_transfer(ctx: Context<CancelProtocolAdminTransfer>) -> Result<()> {
+        ctx.accounts.cancel_protocol_admin_transfer()
+    }
+
+    pub fn create_pool(ctx: Context<CreatePool>, params: PoolParams) -> Result<()> {
+        ctx.accounts.create_pool(params, &ctx.bumps)
+    }
+
+    pub fn create_treasury_pool(ctx: Context<CreateTreasuryPool>, params: PoolParams) -> Result<()> {
+        ctx.accounts.create_treasury_pool(params, &ctx.bumps)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>,points_per_nft_stake: Option<u8>,
+        points_per_spl_stake: Option<u8>,
+        points_per_sol_stake: Option<u8>,
+        min_freeze_period: Option<u32>,
+        emission_per_second: Option<u64>) -> Result<()> {
+        ctx.accounts.update_config(points_per_nft_stake, points_per_spl_stake, points_per_sol_stake, min_freeze_period, emission_per_second)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        ctx.accounts.cancel_admin_transfer()
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: u8) -> Result<()> {
+        ctx.accounts.set_paused(paused)
+    }
+
+    pub fn set_emission_schedule(ctx: Context<SetEmissionSchedule>, schedule: EmissionSchedule) -> Result<()> {
+        ctx.accounts.set_emission_schedule(schedule)
+    }
+
+    pub fn top_up_reward_budget(ctx: Context<TopUpRewardBudget>, amount: u64) -> Result<()> {
+        ctx.accounts.top_up_reward_budget(amount)
+    }
+
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.fund_rewards(amount)
+    }
+
+    pub fn withdraw_unallocated_rewards(ctx: Context<WithdrawUnallocatedRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_unallocated_rewards(amount)
+    }
+
+    pub fn add_reward_token(ctx: Context<AddRewardToken>, mode: RewardMode, emission_per_second: u64, max_reward_supply: u64, reward_budget: u64) -> Result<()> {
+        ctx.accounts.add_reward_token(mode, emission_per_second, max_reward_supply, reward_budget)
+    }
+
+    pub fn update_reward_token(ctx: Context<UpdateRewardToken>, index: u8, emission_per_second: u64) -> Result<()> {
+        ctx.accounts.update_reward_token(index, emission_per_second)
+    }
+
+    pub fn fund_reward_token(ctx: Context<FundRewardToken>, index: u8, amount: u64) -> Result<()> {
+        ctx.accounts.fund_reward_token(index, amount)
+    }
+
+    pub fn top_up_reward_token_budget(ctx: Context<TopUpRewardTokenBudget>, index: u8, amount: u64) -> Result<()> {
+        ctx.accounts.top_up_reward_token_budget(index, amount)
+    }
+
+    pub fn withdraw_unallocated_reward_token(ctx: Context<WithdrawUnallocatedRewardToken>, index: u8, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_unallocated_reward_token(index, amount)
+    }
+
+    pub fn set_lock_tiers(ctx: Context<SetLockTiers>, lock_tiers: [LockTierParams; LockTier::COUNT]) -> Result<()> {
+        ctx.accounts.set_lock_tiers(lock_tiers)
+    }
+
+    pub fn set_early_unstake_penalty(ctx: Context<SetEarlyUnstakePenalty>, penalty_bps: u16) -> Result<()> {
+        ctx.accounts.set_early_unstake_penalty(penalty_bps)
+    }
+
+    pub fn withdraw_sol_penalties(ctx: Context<WithdrawSolPenalties>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_sol_penalties(amount, &ctx.bumps)
+    }
+
+    pub fn withdraw_spl_penalties(ctx: Context<WithdrawSplPenalties>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_spl_penalties(amount, &ctx.bumps)
+    }
+
+    pub fn set_unbonding_period(ctx: Context<SetUnbondingPeriod>, unbonding_period: u32) -> Result<()> {
+        ctx.accounts.set_unbonding_period(unbonding_period)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        ctx.accounts.migrate_config()
+    }
+
+    pub fn add_collection(ctx: Context<AddCollection>, points_per_nft_stake: Option<u8>, min_freeze_period: Option<u32>) -> Result<()> {
+        ctx.accounts.add_collection(points_per_nft_stake, min_freeze_period, &ctx.bumps)
+    }
+
+    pub fn update_collection(ctx: Context<UpdateCollection>, points_per_nft_stake: Option<u8>, min_freeze_period: Option<u32>) -> Result<()> {
+        ctx.accounts.update_collection(points_per_nft_stake, min_freeze_period)
+    }
+
+    pub fn set_collection_enabled(ctx: Context<SetCollectionEnabled>, enabled: bool) -> Result<()> {
+        ctx.accounts.set_collection_enabled(enabled)
+    }
+
+    pub fn remove_collection(ctx: Context<RemoveCollection>) -> Result<()> {
+        ctx.accounts.remove_collection()
+    }
+
+    pub fn add_spl_mint(ctx: Context<AddSplMint>, reward_weight: u16) -> Result<()> {
+        ctx.accounts.add_spl_mint(reward_weight, &ctx.bumps)
+    }
+
+    pub fn set_spl_mint_enabled(ctx: Context<SetSplMintEnabled>, enabled: bool) -> Result<()> {
+        ctx.accounts.set_spl_mint_enabled(enabled)
+    }
+
+    pub fn update_spl_mint(ctx: Context<UpdateSplMint>, reward_weight: u16) -> Result<()> {
+        ctx.accounts.update_spl_mint(reward_weight)
+    }
+
+    pub fn remove_spl_mint(ctx: Context<RemoveSplMint>) -> Result<()> {
+        ctx.accounts.remove_spl_mint()
     }
 
     pub fn initialize_user(ctx: Context<InitializeUser>) -> Result<()> {
         ctx.accounts.initialize_user(&ctx.bumps)
     }
 
-    pub fn stake_nft(ctx: Context<StakeNFT>, seed: u64) -> Result<()> {
-        ctx.accounts.stake_nft(seed, &ctx.bumps)
+    pub fn close_user(ctx: Context<CloseUser>) -> Result<()> {
+        ctx.accounts.close_user()
+    }
+
+    pub fn stake_nft(ctx: Context<StakeNFT>, seed: u64, lock_tier: LockTier) -> Result<()> {
+        ctx.accounts.stake_nft(seed, lock_tier, &ctx.bumps)
+    }
+
+    pub fn stake_sol(ctx: Context<StakeSol>,seed: u64,amount: u64, lock_tier: LockTier) -> Result<()> {
+        ctx.accounts.stake_sol(seed,amount, lock_tier, &ctx.bumps)
+    }
+
+    pub fn stake_spl(ctx: Context<StakeSpl>,seed: u64,amount: u64, lock_tier: LockTier) -> Result<()> {
+        ctx.accounts.stake_spl(seed,amount, lock_tier, &ctx.bumps)
+    }
+
+    pub fn increase_stake_sol<'info>(ctx: Context<'_, '_, 'info, 'info, IncreaseStakeSol<'info>>, amount: u64) -> Result<()> {
+        ctx.accounts.increase_stake_sol(amount, ctx.remaining_accounts)
     }
 
-    pub fn stake_sol(ctx: Context<StakeSol>,seed: u64,amount: u64) -> Result<()> {
-        ctx.accounts.stake_sol(seed,amount, &ctx.bumps)
+    pub fn increase_stake_spl<'info>(ctx: Context<'_, '_, 'info, 'info, IncreaseStakeSpl<'info>>, amount: u64) -> Result<()> {
+        ctx.accounts.increase_stake_spl(amount, ctx.remaining_accounts)
     }
 
-    pub fn stake_spl(ctx: Context<StakeSpl>,seed: u64,amount: u64) -> Result<()> {
-        ctx.accounts.stake_spl(seed,amount, &ctx.bumps)
+    pub fn claim_rewards<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimRewards<'info>>) -> Result<()> {
+        ctx.accounts.claim_rewards(ctx.remaining_accounts)
     }
 
     pub fn unstake_nft(ctx: Context<UnStakeNFT>) -> Result<()> {
         ctx.accounts.unstake_nft()
     }
 
-    pub fn unstake_sol(ctx: Context<UnStakeSoL>) -> Result<()> {
-        ctx.accounts.unstake_sol()
+    pub fn unstake_sol<'info>(ctx: Context<'_, '_, 'info, 'info, UnStakeSoL<'info>>, amount: u64) -> Result<()> {
+        ctx.accounts.unstake_sol(amount, ctx.remaining_accounts)
+    }
+
+    pub fn unstake_spl<'info>(ctx: Context<'_, '_, 'info, 'info, UnStakeSpL<'info>>, amount: u64) -> Result<()> {
+        ctx.accounts.unstake_spl(amount, ctx.remaining_accounts)
+    }
+
+    pub fn early_unstake_sol(ctx: Context<EarlyUnstakeSol>) -> Result<()> {
+        ctx.accounts.early_unstake_sol()
+    }
+
+    pub fn early_unstake_spl(ctx: Context<EarlyUnstakeSpl>) -> Result<()> {
+        ctx.accounts.early_unstake_spl()
+    }
+
+    pub fn request_unstake<'info>(ctx: Context<'_, '_, 'info, 'info, RequestUnstake<'info>>) -> Result<()> {
+        ctx.accounts.request_unstake(ctx.remaining_accounts)
+    }
+
+    pub fn withdraw_sol(ctx: Context<WithdrawSol>) -> Result<()> {
+        ctx.accounts.withdraw_sol()
+    }
+
+    pub fn withdraw_spl(ctx: Context<WithdrawSpl>) -> Result<()> {
+        ctx.accounts.withdraw_spl()
+    }
+
+    pub fn migrate_user(ctx: Context<MigrateUser>) -> Result<()> {
+        ctx.accounts.migrate_user()
     }
 
-    pub fn unstake_spl(ctx: Context<UnStakeSpL>) -> Result<()> {
-        ctx.accounts.unstake_spl()
+    pub fn migrate_stake(ctx: Context<MigrateStake>) -> Result<()> {
+        ctx.accounts.migrate_stake()
     }
 
 }
\ No newline at end of file
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::{nft_index_delta, StakeConfigAccount}};
+
+#[account]
+#[derive(InitSpace)]
+pub struct AllowedCollection{
+    pub config: Pubkey,
+    pub collection_mint: Pubkey,
+    pub enabled: bool,
+    // overrides for the pool wide values, None falls back to the config
+    pub points_per_nft_stake: Option<u8>,
+    pub min_freeze_period: Option<u32>,
+    pub staked_count: u64,
+    // multiplier_bps of every NFT staked under the collection added up
+    pub staked_weight: u64,
+    // reward earned by one NFT of this collection, scaled by ACC_PRECISION, and the config
+    // indexes it was last brought up to date against
+    pub reward_index: u128,
+    pub schedule_index_snapshot: u128,
+    pub nft_index_snapshot: u128,
+    pub bump: u8
+}
+
+impl AllowedCollection {
+    pub fn min_freeze_period(&self, config: &StakeConfigAccount) -> u32 {
+        self.min_freeze_period.unwrap_or(config.min_freeze_period)
+    }
+
+    // follows the config indexes, which must already be updated to now
+    pub fn update_index(&mut self, config: &StakeConfigAccount) -> Result<()> {
+        let delta = match self.points_per_nft_stake {
+            Some(points) => {
+                let schedule_delta = config.schedule_index.checked_sub(self.schedule_index_snapshot).ok_or(ErrorCode::Overflow)?;
+                nft_index_delta(points, schedule_delta)?
+            }
+            None => config.nft_reward_index.checked_sub(self.nft_index_snapshot).ok_or(ErrorCode::Overflow)?,
+        };
+        self.reward_index = self.reward_index.checked_add(delta).ok_or(ErrorCode::Overflow)?;
+        self.schedule_index_snapshot = config.schedule_index;
+        self.nft_index_snapshot = config.nft_reward_index;
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::{BPS_DENOMINATOR, REWARD_DECIMALS}, error::ErrorCode, state::StakeConfigAccount};
+
+#[account]
+#[derive(InitSpace)]
+pub struct AllowedSplMint{
+    pub config: Pubkey,
+    pub mint: Pubkey,
+    // reward weight in basis points, 10_000 is 1x
+    pub reward_weight: u16,
+    // 10^decimals of the mint, used to bring amounts onto the reward mint decimals
+    pub decimals_factor: u64,
+    pub enabled: bool,
+    pub staked_amount: u64,
+    pub bump: u8
+}
+
+impl AllowedSplMint {
+    // raw token amount expressed in reward mint base units and scaled by the mint weight
+    pub fn weighted_amount(&self, amount: u64) -> Result<u64> {
+        let weighted = u128::from(amount)
+            .checked_mul(u128::from(self.reward_weight))
+            .and_then(|v| v.checked_mul(10u128.pow(u32::from(REWARD_DECIMALS))))
+            .and_then(|v| v.checked_div(u128::from(self.decimals_factor)))
+            .and_then(|v| v.checked_div(u128::from(BPS_DENOMINATOR)))
+            .ok_or(ErrorCode::Overflow)?;
+        u64::try_from(weighted).or(Err(ErrorCode::Overflow.into()))
+    }
+
+    // pool weight of an SPL position
+    pub fn stake_weight(&self, config: &StakeConfigAccount, amount: u64) -> Result<u64> {
+        let weighted_amount = self.weighted_amount(amount)?;
+        u64::from(config.points_per_spl_stake).checked_mul(weighted_amount).ok_or(ErrorCode::Overflow.into())
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct EmissionSchedule {
+    pub start_time: i64,
+    // 0 keeps emitting forever
+    pub end_time: i64,
+    // the rate halves every `halving_interval` seconds after start, 0 never halves
+    pub halving_interval: i64,
+}
+
+impl EmissionSchedule {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.start_time >= 0, ErrorCode::InvalidEmissionSchedule);
+        require!(self.end_time == 0 || self.end_time > self.start_time, ErrorCode::InvalidEmissionSchedule);
+        require!(self.halving_interval >= 0, ErrorCode::InvalidEmissionSchedule);
+        Ok(())
+    }
+
+    // integral of `rate` per second over [from, to), clipped to the campaign and halved per interval
+    pub fn emitted(&self, rate: u128, from: i64, to: i64) -> Result<u128> {
+        let from = from.max(self.start_time);
+        let to = if self.end_time > 0 { to.min(self.end_time) } else { to };
+        if to <= from {
+            return Ok(0);
+        }
+
+        if self.halving_interval == 0 {
+            let elapsed = u128::try_from(to - from).or(Err(ErrorCode::Overflow))?;
+            return rate.checked_mul(elapsed).ok_or(ErrorCode::Overflow.into());
+        }
+
+        let mut total: u128 = 0;
+        let mut cursor = from;
+        let mut halvings = (from - self.start_time) / self.halving_interval;
+        // after 128 halvings every u128 rate is zero
+        while cursor < to && halvings < 128 {
+            let epoch_end = self.start_time
+                .checked_add(self.halving_interval.checked_mul(halvings + 1).ok_or(ErrorCode::Overflow)?)
+                .ok_or(ErrorCode::Overflow)?;
+            let segment_end = to.min(epoch_end);
+            let elapsed = u128::try_from(segment_end - cursor).or(Err(ErrorCode::Overflow))?;
+            let segment = (rate >> halvings).checked_mul(elapsed).ok_or(ErrorCode::Overflow)?;
+            total = total.checked_add(segment).ok_or(ErrorCode::Overflow)?;
+            cursor = segment_end;
+            halvings += 1;
+        }
+        Ok(total)
+    }
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::{ADMIN, LEGACY_POOL_ID}, state::{EmissionSchedule, PoolParams, StakeConfigAccount, UserAccount}};
+
+// account layouts from before accounts carried a version byte, only read by the
+// migrate_* instructions which rewrite them in the current layout
+
+// the single pool config of the time, at [b"config"] and minting from [b"rewards", config]
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct StakeConfigAccountV0 {
+    pub points_per_nft_stake: u8,
+    pub points_per_sol_stake: u8,
+    pub points_per_spl_stake: u8,
+    pub min_freeze_period: u32,
+    pub reward_bump: u8,
+    pub bump: u8,
+}
+
+impl StakeConfigAccountV0 {
+    // the config had no admin, schedule or cap and every stake was paid up front: the pool
+    // comes over under ADMIN with an open ended schedule, no SOL/SPL emission and, as its mint
+    // was never capped, a budget covering everything not minted yet by `minted`
+    pub fn migrate(self, reward_mint: Pubkey, minted: u64, now: i64) -> StakeConfigAccount {
+        let params = PoolParams {
+            points_per_nft_stake: self.points_per_nft_stake,
+            points_per_spl_stake: self.points_per_spl_stake,
+            points_per_sol_stake: self.points_per_sol_stake,
+            min_freeze_period: self.min_freeze_period,
+            emission_per_second: 0,
+            schedule: EmissionSchedule {
+                start_time: 0,
+                end_time: 0,
+                halving_interval: 0,
+            },
+            max_reward_supply: u64::MAX,
+            reward_budget: u64::MAX - minted,
+        };
+        let mut config = StakeConfigAccount::new(ADMIN, LEGACY_POOL_ID, params, reward_mint, self.reward_bump, self.bump, now);
+        config.rewards_paid = minted;
+        config
+    }
+}
+
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct UserAccountV0 {
+    pub points: u64,
+    pub nft_staked_amount: u64,
+    pub spl_staked_amount: u64,
+    pub sol_staked_amount: u64,
+    pub bump: u8,
+}
+
+// the staked totals are not carried over, legacy unstake_spl added what it paid out and
+// unstake_sol subtracted the vault after emptying it; migrate_stake adds every position back
+impl From<UserAccountV0> for UserAccount {
+    fn from(legacy: UserAccountV0) -> Self {
+        Self {
+            version: UserAccount::VERSION,
+            points: legacy.points,
+            nft_staked_amount: 0,
+            spl_staked_amount: 0,
+            sol_staked_amount: 0,
+            bump: legacy.bump,
+            reserved: [0; 32],
+        }
+    }
+}
+
+// positions of every kind shared this layout, the kind only shows in the PDA they live at:
+// SOL [b"stake", config, owner, seed], NFT [b"stake", config, mint, seed] and
+// SPL [b"stake", config, owner, mint]
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct StakeAccountV0 {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub staked_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub seed: u64,
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::{BPS_DENOMINATOR, SECONDS_PER_DAY}, error::ErrorCode};
+
+// how long a position commits to stay staked, longer locks earn a larger share
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum LockTier {
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl LockTier {
+    pub const COUNT: usize = 4;
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+// lock length and reward multiplier of a tier, set per pool by its admin
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct LockTierParams {
+    pub duration: u32,
+    // reward multiplier in basis points, 10_000 is 1x
+    pub multiplier_bps: u16,
+}
+
+impl LockTierParams {
+    // what pools start with, in LockTier order
+    pub const DEFAULTS: [LockTierParams; LockTier::COUNT] = [
+        LockTierParams { duration: 7 * SECONDS_PER_DAY as u32, multiplier_bps: 10_000 },
+        LockTierParams { duration: 30 * SECONDS_PER_DAY as u32, multiplier_bps: 12_500 },
+        LockTierParams { duration: 90 * SECONDS_PER_DAY as u32, multiplier_bps: 15_000 },
+        LockTierParams { duration: 365 * SECONDS_PER_DAY as u32, multiplier_bps: 20_000 },
+    ];
+
+    // how long a position in the tier stays locked, the pool's freeze period stays the floor
+    pub fn lock_duration(&self, min_freeze_period: u32) -> u32 {
+        self.duration.max(min_freeze_period)
+    }
+
+    // unlock time of a position staked at `now`
+    pub fn unlock_at(&self, now: i64, min_freeze_period: u32) -> Result<i64> {
+        now.checked_add(i64::from(self.lock_duration(min_freeze_period))).ok_or(ErrorCode::Overflow.into())
+    }
+}
+
+// tiers never pay less than 1x and a longer lock never earns less than a shorter one
+pub fn validate_lock_tiers(lock_tiers: &[LockTierParams; LockTier::COUNT]) -> Result<()> {
+    require!(
+        lock_tiers.iter().all(|tier| u64::from(tier.multiplier_bps) >= BPS_DENOMINATOR),
+        ErrorCode::InvalidLockTiers
+    );
+    require!(
+        lock_tiers
+            .windows(2)
+            .all(|pair| pair[0].duration <= pair[1].duration && pair[0].multiplier_bps <= pair[1].multiplier_bps),
+        ErrorCode::InvalidLockTiers
+    );
+    Ok(())
+}
+
+pub fn apply_multiplier(amount: u64, multiplier_bps: u16) -> Result<u64> {
+    let scaled = u128::from(amount)
+        .checked_mul(u128::from(multiplier_bps))
+        .ok_or(ErrorCode::Overflow)?
+        / u128::from(BPS_DENOMINATOR);
+    u64::try_from(scaled).or(Err(ErrorCode::Overflow.into()))
+}
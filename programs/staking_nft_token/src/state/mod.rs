@@ -1,7 +1,21 @@
 pub mod stake_account;
 pub mod stake_config_account;
 pub mod user_account;
+pub mod protocol_config_account;
+pub mod allowed_collection;
+pub mod allowed_spl_mint;
+pub mod emission_schedule;
+pub mod reward_token;
+pub mod lock_tier;
+pub mod legacy;
 
 pub use stake_account::*;
 pub use stake_config_account::*;
-pub use user_account::*;
\ No newline at end of file
+pub use user_account::*;
+pub use protocol_config_account::*;
+pub use allowed_collection::*;
+pub use allowed_spl_mint::*;
+pub use emission_schedule::*;
+pub use reward_token::*;
+pub use lock_tier::*;
+pub use legacy::*;
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolConfigAccount{
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub pool_creation_fee: u64,
+    pub next_pool_id: u64,
+    pub treasury_bump: u8,
+    pub bump: u8
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::RewardMode};
+
+// an extra reward token the pool pays SOL and SPL stakers next to its primary reward,
+// accrued pro-rata to the same staked weight at its own rate
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct RewardToken {
+    pub mint: Pubkey,
+    pub mode: RewardMode,
+    pub emission_per_second: u64,
+    pub acc_reward_per_share: u128,
+    // hard cap on everything paid out of this token, rewards_paid + reward_budget never exceeds it
+    pub max_reward_supply: u64,
+    pub rewards_paid: u64,
+    pub reward_budget: u64,
+    // accrued to open positions but not settled yet, part of the budget that can't be withdrawn
+    pub accrued_rewards: u64,
+}
+
+impl RewardToken {
+    pub fn consume_reward_budget(&mut self, amount: u64) -> Result<()> {
+        require!(amount <= self.reward_budget, ErrorCode::RewardBudgetExhausted);
+        self.reward_budget -= amount;
+        self.rewards_paid = self.rewards_paid.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    pub fn top_up_reward_budget(&mut self, amount: u64) -> Result<()> {
+        let reward_budget = self.reward_budget.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        let committed = self.rewards_paid.checked_add(reward_budget).ok_or(ErrorCode::Overflow)?;
+        require!(committed <= self.max_reward_supply, ErrorCode::RewardSupplyCapExceeded);
+        self.reward_budget = reward_budget;
+        Ok(())
+    }
+
+    pub fn unallocated_rewards(&self) -> u64 {
+        self.reward_budget.saturating_sub(self.accrued_rewards)
+    }
+}
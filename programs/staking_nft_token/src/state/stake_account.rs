@@ -1,12 +1,135 @@
 use anchor_lang::prelude::*;
 
+use crate::{constants::{ACC_PRECISION, MAX_EXTRA_REWARDS}, error::ErrorCode, state::{apply_multiplier, RewardToken}};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum StakeKind {
+    Nft,
+    Spl,
+    Sol,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct StakeAccount{
+    // layout version, bumped whenever fields are added and older accounts need migrate_stake
+    pub version: u8,
     pub owner: Pubkey,
+    pub config: Pubkey,
+    pub kind: StakeKind,
+    // staked mint, the native mint for SOL positions
     pub mint: Pubkey,
+    // collection the NFT was staked under, None for SOL and SPL positions
+    pub collection: Option<Pubkey>,
     pub staked_at: i64,
+    pub last_claimed_at: i64,
+    // picked through the lock tier at stake time
+    pub unlock_at: i64,
+    pub multiplier_bps: u16,
+    // set by request_unstake, 0 while the position is still earning
+    pub withdrawable_at: i64,
+    // weight against the position's reward index (the pool accumulator for SOL/SPL, the
+    // collection index for NFTs) and what it had already earned at the last checkpoint
+    pub weight: u64,
+    pub reward_debt: u128,
+    // principal still staked: lamports for SOL, base units for SPL, 1 for an NFT
+    pub amount: u64,
+    // reward debt per extra reward token of the pool, in the config's order; tokens
+    // registered after the last checkpoint have no entry yet and start from zero
+    #[max_len(MAX_EXTRA_REWARDS)]
+    pub extra_reward_debts: Vec<u128>,
     pub bump: u8,
     pub vault_bump: u8,
-    pub seed: u64
+    pub seed: u64,
+    // SPL positions staked before the seed was part of their PDA, [b"stake", config, owner, mint]
+    pub legacy_seeds: bool,
+    // lock the position's tier gave it at stake time, what top-ups are locked for; 0 on
+    // version 1 positions until their first top-up records it
+    pub lock_duration: u32,
+    // room for new fields without a realloc, legacy_seeds and lock_duration took the first bytes
+    pub reserved: [u8; 27],
+}
+
+impl StakeAccount {
+    pub const VERSION: u8 = 2;
+
+    // SOL/SPL positions carry their multiplier in their weight, NFT positions have a
+    // fixed weight of 1 and get it applied to what they are paid instead
+    pub fn apply_multiplier(&self, amount: u64) -> Result<u64> {
+        apply_multiplier(amount, self.multiplier_bps)
+    }
+
+    // rewards earned since the last checkpoint against the position's reward index
+    pub fn pending_rewards(&self, reward_index: u128) -> Result<u64> {
+        let earned = self.accumulated(reward_index)?;
+        let pending = earned.checked_sub(self.reward_debt).ok_or(ErrorCode::Overflow)?;
+        u64::try_from(pending).or(Err(ErrorCode::Overflow.into()))
+    }
+
+    pub fn checkpoint_rewards(&mut self, reward_index: u128) -> Result<()> {
+        self.reward_debt = self.accumulated(reward_index)?;
+        Ok(())
+    }
+
+    // `amount` topped up at `now` is locked for the position's tier duration, the rest of
+    // the position for what is left of its lock; the unlock time is their average by amount
+    pub fn extend_lock(&mut self, now: i64, amount: u64) -> Result<()> {
+        if self.version < 2 {
+            // version 1 did not record the duration, the span of its current lock is the best left
+            let duration = self.unlock_at.checked_sub(self.staked_at).ok_or(ErrorCode::Overflow)?;
+            self.lock_duration = u32::try_from(duration).or(Err(ErrorCode::Overflow))?;
+            self.version = Self::VERSION;
+        }
+        let duration = i64::from(self.lock_duration);
+        let remaining = self.unlock_at.saturating_sub(now).max(0);
+        let lock = (i128::from(remaining) * i128::from(self.amount) + i128::from(duration) * i128::from(amount))
+            / (i128::from(self.amount) + i128::from(amount));
+        let unlock_at = now.checked_add(i64::try_from(lock).or(Err(ErrorCode::Overflow))?).ok_or(ErrorCode::Overflow)?;
+        self.unlock_at = self.unlock_at.max(unlock_at);
+        Ok(())
+    }
+
+    // the seed as it goes into the position's PDA; legacy SPL positions pass it empty, which
+    // derives the same address as leaving it out, so one set of accounts serves both layouts
+    pub fn seed_bytes(&self) -> Vec<u8> {
+        if self.legacy_seeds {
+            return Vec::new();
+        }
+        self.seed.to_le_bytes().to_vec()
+    }
+
+    // part of the weight backing `amount` of the principal, the last of it takes what is left
+    pub fn weight_share(&self, amount: u64) -> Result<u64> {
+        if amount == self.amount {
+            return Ok(self.weight);
+        }
+        let share = u128::from(self.weight)
+            .checked_mul(u128::from(amount))
+            .ok_or(ErrorCode::Overflow)?
+            / u128::from(self.amount);
+        u64::try_from(share).or(Err(ErrorCode::Overflow.into()))
+    }
+
+    pub fn pending_extra_rewards(&self, index: usize, reward_token: &RewardToken) -> Result<u64> {
+        let earned = self.accumulated(reward_token.acc_reward_per_share)?;
+        let debt = self.extra_reward_debts.get(index).copied().unwrap_or(0);
+        let pending = earned.checked_sub(debt).ok_or(ErrorCode::Overflow)?;
+        u64::try_from(pending).or(Err(ErrorCode::Overflow.into()))
+    }
+
+    pub fn checkpoint_extra_rewards(&mut self, extra_rewards: &[RewardToken]) -> Result<()> {
+        self.extra_reward_debts = extra_rewards
+            .iter()
+            .map(|extra| self.accumulated(extra.acc_reward_per_share))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(())
+    }
+
+    fn accumulated(&self, reward_index: u128) -> Result<u128> {
+        let accumulated = u128::from(self.weight)
+            .checked_mul(reward_index)
+            .ok_or(ErrorCode::Overflow)?
+            / ACC_PRECISION;
+        Ok(accumulated)
+    }
 }
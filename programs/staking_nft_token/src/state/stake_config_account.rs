@@ -1,12 +1,286 @@
 use anchor_lang::prelude::*;
 
+use anchor_spl::token::spl_token::native_mint;
+
+use crate::{constants::{ACC_PRECISION, BPS_DENOMINATOR, LEGACY_POOL_ID, MAX_EXTRA_REWARDS, PAUSE_FULL_FREEZE, REWARD_DECIMALS, SECONDS_PER_DAY}, error::ErrorCode, state::{EmissionSchedule, LockTier, LockTierParams, RewardToken, StakeAccount}};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RewardMode {
+    // rewards are minted from the [b"rewards", config] mint the config is authority of
+    Mint,
+    // rewards are an existing token paid out of a config owned treasury funded up front
+    Treasury,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct StakeConfigAccount{
+    // layout version, bumped whenever fields are added and older accounts need migrate_config
+    pub version: u8,
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub pool_id: u64,
     pub points_per_nft_stake: u8,
     pub points_per_sol_stake: u8,
     pub points_per_spl_stake: u8,
     pub min_freeze_period: u32,
+    pub paused: u8,
+    // SOL and SPL stakers share `emission_per_second` pro-rata to their weight
+    pub emission_per_second: u64,
+    // scales every accrual, NFT rates and the SOL/SPL emission alike
+    pub schedule: EmissionSchedule,
+    pub total_staked_weight: u64,
+    pub acc_reward_per_share: u128,
+    pub last_reward_at: i64,
+    // cumulative indexes checkpointed before any rate or schedule change so that accrual
+    // integrates each rate only over the time it was in force:
+    // schedule_index is the elapsed emission-schedule time, nft_reward_index the reward
+    // earned by one NFT at the pool default rate, both scaled by ACC_PRECISION
+    pub schedule_index: u128,
+    pub nft_reward_index: u128,
+    pub index_updated_at: i64,
+    // staked NFTs summed by multiplier_bps, for collections on the pool rate and, scaled by
+    // their own rate, for collections with an override; they drive the NFT side of accrued_rewards
+    pub nft_default_weight: u64,
+    pub nft_override_weight: u64,
+    // hard cap on everything the pool will ever pay out, rewards_paid + reward_budget never exceeds it
+    pub max_reward_supply: u64,
+    pub rewards_paid: u64,
+    // what may still be paid out before the budget is topped up (mint mode) or funded (treasury mode)
+    pub reward_budget: u64,
+    // accrued to open positions but not settled yet, part of the budget that can't be withdrawn
+    pub accrued_rewards: u64,
+    pub reward_mode: RewardMode,
+    pub reward_mint: Pubkey,
     pub reward_bump: u8,
-    pub bump: u8
-}
\ No newline at end of file
+    // accrue on the same weight and schedule as the primary reward, NFT positions do not earn them
+    #[max_len(MAX_EXTRA_REWARDS)]
+    pub extra_rewards: Vec<RewardToken>,
+    // share of the principal kept by the [b"penalty", config] vault when a locked position exits early
+    pub early_unstake_penalty_bps: u16,
+    // when non zero SOL/SPL positions leave through request_unstake and wait this long to withdraw
+    pub unbonding_period: u32,
+    // duration and multiplier of every LockTier, in its order
+    pub lock_tiers: [LockTierParams; LockTier::COUNT],
+    pub bump: u8,
+    // room for new fields without a realloc, max_reward_supply of the extra rewards took 24 bytes
+    pub reserved: [u8; 40],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PoolParams {
+    pub points_per_nft_stake: u8,
+    pub points_per_spl_stake: u8,
+    pub points_per_sol_stake: u8,
+    pub min_freeze_period: u32,
+    pub emission_per_second: u64,
+    pub schedule: EmissionSchedule,
+    pub max_reward_supply: u64,
+    pub reward_budget: u64,
+}
+
+impl StakeConfigAccount {
+    pub const VERSION: u8 = 1;
+
+    pub fn new(admin: Pubkey, pool_id: u64, params: PoolParams, reward_mint: Pubkey, reward_bump: u8, bump: u8, now: i64) -> Self {
+        Self {
+            version: Self::VERSION,
+            admin,
+            pending_admin: None,
+            pool_id,
+            points_per_nft_stake: params.points_per_nft_stake,
+            points_per_sol_stake: params.points_per_sol_stake,
+            points_per_spl_stake: params.points_per_spl_stake,
+            min_freeze_period: params.min_freeze_period,
+            paused: 0,
+            emission_per_second: params.emission_per_second,
+            schedule: params.schedule,
+            total_staked_weight: 0,
+            acc_reward_per_share: 0,
+            last_reward_at: now,
+            schedule_index: 0,
+            nft_reward_index: 0,
+            index_updated_at: now,
+            nft_default_weight: 0,
+            nft_override_weight: 0,
+            max_reward_supply: params.max_reward_supply,
+            rewards_paid: 0,
+            reward_budget: params.reward_budget,
+            accrued_rewards: 0,
+            reward_mode: RewardMode::Mint,
+            reward_mint,
+            reward_bump,
+            extra_rewards: Vec::new(),
+            early_unstake_penalty_bps: 0,
+            unbonding_period: 0,
+            lock_tiers: LockTierParams::DEFAULTS,
+            bump,
+            reserved: [0; 40],
+        }
+    }
+
+    // the pool id as it goes into the config PDA; the legacy config passes it empty, which
+    // derives the same address as leaving it out
+    pub fn pool_seed(&self) -> Vec<u8> {
+        if self.pool_id == LEGACY_POOL_ID {
+            return Vec::new();
+        }
+        self.pool_id.to_le_bytes().to_vec()
+    }
+
+    pub fn require_not_paused(&self, flag: u8, error: ErrorCode) -> Result<()> {
+        require!(self.paused & PAUSE_FULL_FREEZE == 0, ErrorCode::ProgramFrozen);
+        if self.paused & flag != 0 {
+            return Err(error.into());
+        }
+        Ok(())
+    }
+
+    // every reward payout has to go through here
+    pub fn consume_reward_budget(&mut self, amount: u64) -> Result<()> {
+        require!(amount <= self.reward_budget, ErrorCode::RewardBudgetExhausted);
+        self.reward_budget -= amount;
+        self.rewards_paid = self.rewards_paid.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    pub fn top_up_reward_budget(&mut self, amount: u64) -> Result<()> {
+        let reward_budget = self.reward_budget.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        let committed = self.rewards_paid.checked_add(reward_budget).ok_or(ErrorCode::Overflow)?;
+        require!(committed <= self.max_reward_supply, ErrorCode::RewardSupplyCapExceeded);
+        self.reward_budget = reward_budget;
+        Ok(())
+    }
+
+    // brings the accumulator up to `now`, must run before the staked weight or emission changes
+    pub fn update_pool(&mut self, now: i64) -> Result<()> {
+        if now <= self.last_reward_at {
+            return Ok(());
+        }
+        if self.total_staked_weight > 0 {
+            let emitted = self.schedule.emitted(u128::from(self.emission_per_second), self.last_reward_at, now)?;
+            let increase = emitted
+                .checked_mul(ACC_PRECISION)
+                .ok_or(ErrorCode::Overflow)?
+                / u128::from(self.total_staked_weight);
+            self.acc_reward_per_share = self.acc_reward_per_share.checked_add(increase).ok_or(ErrorCode::Overflow)?;
+            self.accrued_rewards = saturating_accrue(self.accrued_rewards, emitted);
+
+            for extra in self.extra_rewards.iter_mut() {
+                let emitted = self.schedule.emitted(u128::from(extra.emission_per_second), self.last_reward_at, now)?;
+                let increase = emitted
+                    .checked_mul(ACC_PRECISION)
+                    .ok_or(ErrorCode::Overflow)?
+                    / u128::from(self.total_staked_weight);
+                extra.acc_reward_per_share = extra.acc_reward_per_share.checked_add(increase).ok_or(ErrorCode::Overflow)?;
+                extra.accrued_rewards = saturating_accrue(extra.accrued_rewards, emitted);
+            }
+        }
+        self.last_reward_at = now;
+        Ok(())
+    }
+
+    // brings the NFT indexes up to `now`, must run before the default NFT rate or schedule changes
+    pub fn update_indexes(&mut self, now: i64) -> Result<()> {
+        if now <= self.index_updated_at {
+            return Ok(());
+        }
+        let schedule_delta = self.schedule.emitted(ACC_PRECISION, self.index_updated_at, now)?;
+        let nft_delta = nft_index_delta(self.points_per_nft_stake, schedule_delta)?;
+
+        // what every staked NFT earned over the step, rounded up so the liability is never short
+        let default_accrued = nft_delta.checked_mul(u128::from(self.nft_default_weight)).ok_or(ErrorCode::Overflow)?;
+        let override_accrued = nft_index_delta(1, schedule_delta.checked_mul(u128::from(self.nft_override_weight)).ok_or(ErrorCode::Overflow)?)?;
+        let nft_accrued = default_accrued
+            .checked_add(override_accrued)
+            .ok_or(ErrorCode::Overflow)?
+            .div_ceil(ACC_PRECISION * u128::from(BPS_DENOMINATOR));
+        self.accrued_rewards = saturating_accrue(self.accrued_rewards, nft_accrued);
+
+        self.schedule_index = self.schedule_index.checked_add(schedule_delta).ok_or(ErrorCode::Overflow)?;
+        self.nft_reward_index = self.nft_reward_index.checked_add(nft_delta).ok_or(ErrorCode::Overflow)?;
+        self.index_updated_at = now;
+        Ok(())
+    }
+
+    // settled positions come off the liability whether they were paid or forfeited
+    pub fn release_accrued_rewards(&mut self, amount: u64) {
+        self.accrued_rewards = self.accrued_rewards.saturating_sub(amount);
+    }
+
+    // drops everything a position earned without paying it out, the pool must be updated to now
+    pub fn forfeit_rewards(&mut self, stake_account: &StakeAccount) -> Result<()> {
+        let pending = stake_account.pending_rewards(self.acc_reward_per_share)?;
+        self.release_accrued_rewards(pending);
+        for (index, extra) in self.extra_rewards.iter_mut().enumerate() {
+            let pending = stake_account.pending_extra_rewards(index, extra)?;
+            extra.accrued_rewards = extra.accrued_rewards.saturating_sub(pending);
+        }
+        Ok(())
+    }
+
+    // NFT weight entering or leaving the pool, `points` is the rate override of its collection
+    pub fn add_nft_weight(&mut self, points: Option<u8>, weight: u64) -> Result<()> {
+        match points {
+            Some(points) => {
+                let weight = weight.checked_mul(u64::from(points)).ok_or(ErrorCode::Overflow)?;
+                self.nft_override_weight = self.nft_override_weight.checked_add(weight).ok_or(ErrorCode::Overflow)?;
+            }
+            None => self.nft_default_weight = self.nft_default_weight.checked_add(weight).ok_or(ErrorCode::Overflow)?,
+        }
+        Ok(())
+    }
+
+    pub fn remove_nft_weight(&mut self, points: Option<u8>, weight: u64) -> Result<()> {
+        match points {
+            Some(points) => {
+                let weight = weight.checked_mul(u64::from(points)).ok_or(ErrorCode::Overflow)?;
+                self.nft_override_weight = self.nft_override_weight.checked_sub(weight).ok_or(ErrorCode::Overflow)?;
+            }
+            None => self.nft_default_weight = self.nft_default_weight.checked_sub(weight).ok_or(ErrorCode::Overflow)?,
+        }
+        Ok(())
+    }
+
+    // budget not owed to anyone yet, indexes and pool must be updated to now
+    pub fn unallocated_rewards(&self) -> u64 {
+        self.reward_budget.saturating_sub(self.accrued_rewards)
+    }
+
+    pub fn lock_tier(&self, lock_tier: LockTier) -> LockTierParams {
+        self.lock_tiers[lock_tier.index()]
+    }
+
+    pub fn early_unstake_penalty(&self, amount: u64) -> Result<u64> {
+        let penalty = u128::from(amount)
+            .checked_mul(u128::from(self.early_unstake_penalty_bps))
+            .ok_or(ErrorCode::Overflow)?
+            / u128::from(BPS_DENOMINATOR);
+        u64::try_from(penalty).or(Err(ErrorCode::Overflow.into()))
+    }
+
+    // pool weight of a SOL position, lamports brought onto the reward mint decimals
+    pub fn sol_stake_weight(&self, lamports: u64) -> Result<u64> {
+        let weight = u128::from(lamports)
+            .checked_mul(u128::from(self.points_per_sol_stake))
+            .and_then(|v| v.checked_mul(10u128.pow(u32::from(REWARD_DECIMALS))))
+            .ok_or(ErrorCode::Overflow)?
+            / 10u128.pow(u32::from(native_mint::DECIMALS));
+        u64::try_from(weight).or(Err(ErrorCode::Overflow.into()))
+    }
+}
+
+// liabilities only have to err on the high side, past u64 they simply stay at the max
+pub fn saturating_accrue(accrued: u64, amount: u128) -> u64 {
+    accrued.saturating_add(u64::try_from(amount).unwrap_or(u64::MAX))
+}
+
+// reward index growth of one NFT earning `points_per_nft_stake` per day over `schedule_delta`
+pub fn nft_index_delta(points_per_nft_stake: u8, schedule_delta: u128) -> Result<u128> {
+    let delta = schedule_delta
+        .checked_mul(u128::from(points_per_nft_stake))
+        .and_then(|v| v.checked_mul(10u128.pow(u32::from(REWARD_DECIMALS))))
+        .ok_or(ErrorCode::Overflow)?
+        / u128::from(SECONDS_PER_DAY);
+    Ok(delta)
+}
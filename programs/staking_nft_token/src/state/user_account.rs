@@ -3,9 +3,17 @@ use anchor_lang::prelude::*;
 #[account]
 #[derive(InitSpace)]
 pub struct UserAccount{
+    // layout version, bumped whenever fields are added and older accounts need migrate_user
+    pub version: u8,
     pub points: u64,
     pub nft_staked_amount: u64,
     pub spl_staked_amount: u64,
     pub sol_staked_amount: u64,
-    pub bump: u8
+    pub bump: u8,
+    // room for new fields without a realloc
+    pub reserved: [u8; 32],
+}
+
+impl UserAccount {
+    pub const VERSION: u8 = 1;
 }
@@ -0,0 +1,213 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{associated_token::get_associated_token_address, token::{mint_to, transfer_checked, Mint, MintTo, Token, TokenAccount, TransferChecked}};
+
+use crate::{error::ErrorCode, state::{RewardMode, StakeAccount, StakeConfigAccount, UserAccount}};
+
+// pays `amount` of the pool reward token to `to`, minting it or moving it out of the
+// config owned treasury depending on the pool reward mode
+pub fn pay_rewards<'info>(
+    config: &mut Account<'info, StakeConfigAccount>,
+    reward_mint: &Account<'info, Mint>,
+    reward_treasury: Option<&Account<'info, TokenAccount>>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    config.consume_reward_budget(amount)?;
+
+    let pool_seed = config.pool_seed();
+    let seeds = &[&b"config"[..], &pool_seed[..], &[config.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    match config.reward_mode {
+        RewardMode::Mint => {
+            let cpi_accounts = MintTo {
+                mint: reward_mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: config.to_account_info(),
+            };
+            let ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+            mint_to(ctx, amount)
+        }
+        RewardMode::Treasury => {
+            let reward_treasury = reward_treasury.ok_or(ErrorCode::MissingRewardTreasury)?;
+            let cpi_accounts = TransferChecked {
+                from: reward_treasury.to_account_info(),
+                mint: reward_mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: config.to_account_info(),
+            };
+            let ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+            transfer_checked(ctx, amount, reward_mint.decimals)
+        }
+    }
+}
+
+// the accounts the pool reward token of a position is paid through, see pay_rewards
+pub struct RewardPayout<'a, 'info> {
+    pub reward_mint: &'a Account<'info, Mint>,
+    pub reward_treasury: Option<&'a Account<'info, TokenAccount>>,
+    pub to: &'a Account<'info, TokenAccount>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+// what settling a position does with rewards a budget cannot cover
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Shortfall {
+    // drop them, for exits which must not lock principal in behind an empty budget
+    Forfeit,
+    // leave the token unsettled so the position can claim it after a top-up
+    Defer,
+    // fail with RewardBudgetExhausted
+    Reject,
+}
+
+// brings the pool up to `now` and pays a SOL/SPL position everything it earned so far of the
+// pool reward, credited to the owner's points, and of the extra reward tokens; `shortfall`
+// is Forfeit or Reject, the pool reward has no debt to defer against
+pub fn settle_position<'info>(
+    config: &mut Account<'info, StakeConfigAccount>,
+    stake_account: &mut StakeAccount,
+    user_account: &mut UserAccount,
+    payout: RewardPayout<'_, 'info>,
+    reward_accounts: &'info [AccountInfo<'info>],
+    now: i64,
+    shortfall: Shortfall,
+) -> Result<()> {
+    config.update_pool(now)?;
+    let reward_amount = stake_account.pending_rewards(config.acc_reward_per_share)?;
+    config.release_accrued_rewards(reward_amount);
+    let reward_amount = match shortfall {
+        Shortfall::Forfeit => reward_amount.min(config.reward_budget),
+        _ => reward_amount,
+    };
+    if reward_amount > 0 {
+        pay_rewards(config, payout.reward_mint, payout.reward_treasury, payout.to, payout.token_program, reward_amount)?;
+        user_account.points = user_account.points.checked_add(reward_amount).ok_or(ErrorCode::Overflow)?;
+    }
+    stake_account.last_claimed_at = now;
+    settle_extra_rewards(config, stake_account, reward_accounts, payout.token_program, shortfall)
+}
+
+// pays a SOL/SPL position everything it earned of the pool's extra reward tokens and
+// checkpoints it, `reward_accounts` holds a (reward mint, owner token account, config
+// treasury) triple per extra token in config order; the treasury slot is unused in mint mode
+pub fn settle_extra_rewards<'info>(
+    config: &mut Account<'info, StakeConfigAccount>,
+    stake_account: &mut StakeAccount,
+    reward_accounts: &'info [AccountInfo<'info>],
+    token_program: &Program<'info, Token>,
+    shortfall: Shortfall,
+) -> Result<()> {
+    let pool_seed = config.pool_seed();
+    let bump = [config.bump];
+    let seeds = &[&b"config"[..], &pool_seed[..], &bump[..]];
+    let signer_seeds = &[&seeds[..]];
+    let config_info = config.to_account_info();
+    let mut deferred = Vec::new();
+
+    for index in 0..config.extra_rewards.len() {
+        let extra = &mut config.extra_rewards[index];
+        let pending = stake_account.pending_extra_rewards(index, extra)?;
+        if pending > extra.reward_budget {
+            match shortfall {
+                Shortfall::Forfeit => {}
+                Shortfall::Defer => {
+                    deferred.push((index, stake_account.extra_reward_debts.get(index).copied().unwrap_or(0)));
+                    continue;
+                }
+                Shortfall::Reject => return err!(ErrorCode::RewardBudgetExhausted),
+            }
+        }
+        extra.accrued_rewards = extra.accrued_rewards.saturating_sub(pending);
+        let amount = pending.min(extra.reward_budget);
+        if amount == 0 {
+            continue;
+        }
+        extra.consume_reward_budget(amount)?;
+
+        let accounts = reward_accounts.get(index * 3..index * 3 + 3).ok_or(ErrorCode::InvalidRewardAccounts)?;
+        let reward_mint = Account::<Mint>::try_from(&accounts[0])?;
+        let to = Account::<TokenAccount>::try_from(&accounts[1])?;
+        require_keys_eq!(reward_mint.key(), extra.mint, ErrorCode::InvalidRewardAccounts);
+        require_keys_eq!(to.mint, extra.mint, ErrorCode::InvalidRewardAccounts);
+        require_keys_eq!(to.owner, stake_account.owner, ErrorCode::InvalidRewardAccounts);
+
+        match extra.mode {
+            RewardMode::Mint => {
+                let cpi_accounts = MintTo {
+                    mint: reward_mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: config_info.clone(),
+                };
+                let ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+                mint_to(ctx, amount)?;
+            }
+            RewardMode::Treasury => {
+                require_keys_eq!(
+                    accounts[2].key(),
+                    get_associated_token_address(&config_info.key(), &extra.mint),
+                    ErrorCode::InvalidRewardAccounts
+                );
+                let cpi_accounts = TransferChecked {
+                    from: accounts[2].clone(),
+                    mint: reward_mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: config_info.clone(),
+                };
+                let ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+                transfer_checked(ctx, amount, reward_mint.decimals)?;
+            }
+        }
+    }
+
+    stake_account.checkpoint_extra_rewards(&config.extra_rewards)?;
+    for (index, debt) in deferred {
+        stake_account.extra_reward_debts[index] = debt;
+    }
+    Ok(())
+}
+
+// contents of a program account still in the pre-versioning layout of `T`, `space` is what
+// it was allocated with at the time
+pub fn read_legacy<T: AnchorDeserialize>(info: &AccountInfo, discriminator: &[u8], space: usize, current_space: usize) -> Result<T> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::UnknownAccountLayout);
+    let data = info.try_borrow_data()?;
+    require!(data.len() != current_space, ErrorCode::AccountAlreadyMigrated);
+    require!(data.len() == space && data.starts_with(discriminator), ErrorCode::UnknownAccountLayout);
+    T::deserialize(&mut &data[8..]).or(Err(ErrorCode::UnknownAccountLayout.into()))
+}
+
+// rewrites `info` in place as `account`, growing it to `space` and topping up its rent from `payer`
+pub fn migrate_account<'info, T: AccountSerialize>(
+    info: &AccountInfo<'info>,
+    account: &T,
+    space: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let rent_exempt = Rent::get()?.minimum_balance(space);
+    let missing = rent_exempt.saturating_sub(info.lamports());
+    if missing > 0 {
+        let cpi_accounts = Transfer {
+            from: payer.to_account_info(),
+            to: info.clone(),
+        };
+        let ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+        transfer(ctx, missing)?;
+    }
+
+    info.realloc(space, true)?;
+    let mut data = info.try_borrow_mut_data()?;
+    account.try_serialize(&mut &mut data[..])
+}
+
+// closes a legacy program account which is not migrated, refunding its rent to `destination`
+pub fn close_legacy<'info>(info: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    let lamports = destination.lamports().checked_add(info.lamports()).ok_or(ErrorCode::Overflow)?;
+    **destination.try_borrow_mut_lamports()? = lamports;
+    **info.try_borrow_mut_lamports()? = 0;
+
+    info.assign(&System::id());
+    info.realloc(0, false).map_err(Into::into)
+}
@@ -14,6 +14,8 @@ import {
   getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
   mintTo,
+  setAuthority,
+  AuthorityType,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
@@ -39,11 +41,33 @@ describe("staking_nft_token", () => {
   let splMint: PublicKey;
   let splStakeAccount: PublicKey;
   let vaultAta: PublicKey;
+  let allowedSplMint: PublicKey;
+  let partnerRewardMint: PublicKey;
+  let partnerRewardTreasury: PublicKey;
+  let treasuryPoolConfig: PublicKey;
+  let treasuryPoolMint: PublicKey;
+
+  // (reward mint, user token account, config treasury) for every extra reward token
+  async function extraRewardAccounts() {
+    const userPartnerAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      user,
+      partnerRewardMint,
+      user.publicKey
+    );
+    return [
+      { pubkey: partnerRewardMint, isSigner: false, isWritable: true },
+      { pubkey: userPartnerAta.address, isSigner: false, isWritable: true },
+      { pubkey: partnerRewardTreasury, isSigner: false, isWritable: true },
+    ];
+  }
 
   let seed = new BN(randomBytes(8));
+  // admin pool ids sit below the range handed out to permissionless pools
+  const poolId = new BN(randomBytes(4));
 
   const [config] = PublicKey.findProgramAddressSync(
-    [Buffer.from("config")],
+    [Buffer.from("config"), poolId.toArrayLike(Buffer, "le", 8)],
     programId
   );
 
@@ -64,14 +88,9 @@ describe("staking_nft_token", () => {
   }
 
   before(async () => {
-    admin = Keypair.generate();
-    const transfer = SystemProgram.transfer({
-      fromPubkey: provider.publicKey,
-      toPubkey: admin.publicKey,
-      lamports: 10 * LAMPORTS_PER_SOL,
-    });
-    const tx = new Transaction().add(transfer);
-    await provider.sendAndConfirm(tx);
+    // initialize_config is gated on the ADMIN constant, so the local wallet
+    // configured in Anchor.toml has to be that key.
+    admin = (provider.wallet as anchor.Wallet).payer;
 
     user = Keypair.generate();
     const transfer2 = SystemProgram.transfer({
@@ -82,180 +101,2124 @@ describe("staking_nft_token", () => {
     const tx2 = new Transaction().add(transfer2);
     await provider.sendAndConfirm(tx2);
 
-    [userAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user"), user.publicKey.toBuffer()],
+    [userAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), user.publicKey.toBuffer()],
+      programId
+    );
+
+    [stakeAccount] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        config.toBuffer(),
+        user.publicKey.toBuffer(),
+        seed.toArrayLike(Buffer, "le", 8)
+      ],
+      programId
+    );
+
+    [vault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), stakeAccount.toBuffer()],
+      programId
+    );
+
+    [rewardMintPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rewards"), config.toBuffer()],
+      programId
+    );
+  });
+
+  it("initialized config", async () => {
+    try {
+      const tx = await program.methods
+        .initializeConfig(poolId, {
+          pointsPerNftStake: 8,
+          pointsPerSplStake: 4,
+          pointsPerSolStake: 2,
+          minFreezePeriod: 86400,
+          emissionPerSecond: new BN(1_000_000),
+          schedule: {
+            startTime: new BN(0),
+            endTime: new BN(0),
+            halvingInterval: new BN(0),
+          },
+          maxRewardSupply: new BN("1000000000000000"),
+          rewardBudget: new BN("100000000000000"),
+        })
+        .accountsPartial({
+          admin: admin.publicKey,
+          config: config,
+          rewardMint: rewardMintPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+      await confirm(tx);
+      await log(tx);
+
+      console.log("Config initialized successfully!");
+      const configAccount = await program.account.stakeConfigAccount.fetch(
+        config
+      );
+      console.log("Config account data:", configAccount);
+    } catch (error) {
+      console.error("Error initializing config:", error);
+      throw error;
+    }
+  });
+
+  it("update config", async () => {
+    const tx = await program.methods
+      .updateConfig(10, null, 3, null, null)
+      .accountsPartial({
+        admin: admin.publicKey,
+        config: config,
+      })
+      .signers([admin])
+      .rpc();
+    await confirm(tx);
+    await log(tx);
+
+    const configAccount = await program.account.stakeConfigAccount.fetch(
+      config
+    );
+    if (
+      configAccount.pointsPerNftStake !== 10 ||
+      configAccount.pointsPerSolStake !== 3
+    ) {
+      throw new Error("updated points rates should be stored on config");
+    }
+    if (
+      configAccount.pointsPerSplStake !== 4 ||
+      configAccount.minFreezePeriod !== 86400 ||
+      configAccount.emissionPerSecond.toNumber() !== 1_000_000
+    ) {
+      throw new Error("fields passed as null should be left unchanged");
+    }
+  });
+
+  it("sets a halving emission schedule", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .setEmissionSchedule({
+        startTime: new BN(now),
+        endTime: new BN(now + 365 * 86400),
+        halvingInterval: new BN(90 * 86400),
+      })
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    const { schedule } = await program.account.stakeConfigAccount.fetch(config);
+    if (
+      schedule.startTime.toNumber() !== now ||
+      schedule.endTime.toNumber() !== now + 365 * 86400 ||
+      schedule.halvingInterval.toNumber() !== 90 * 86400
+    ) {
+      throw new Error("the emission schedule should be stored on the config");
+    }
+  });
+
+  it("rejects an emission schedule ending before it starts", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    try {
+      await program.methods
+        .setEmissionSchedule({
+          startTime: new BN(now),
+          endTime: new BN(now),
+          halvingInterval: new BN(0),
+        })
+        .accountsPartial({ admin: admin.publicKey, config })
+        .signers([admin])
+        .rpc();
+      throw new Error("a schedule with end_time <= start_time should be rejected");
+    } catch (error) {
+      if (!error.toString().includes("InvalidEmissionSchedule")) {
+        throw error;
+      }
+    }
+  });
+
+  it("tops up the reward budget within the supply cap", async () => {
+    await program.methods
+      .topUpRewardBudget(new BN("100000000000000"))
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    try {
+      await program.methods
+        .topUpRewardBudget(new BN("1000000000000000"))
+        .accountsPartial({ admin: admin.publicKey, config })
+        .signers([admin])
+        .rpc();
+      throw new Error("top up above the max supply should have failed");
+    } catch (error) {
+      if (!error.toString().includes("RewardSupplyCapExceeded")) {
+        throw error;
+      }
+    }
+  });
+
+  it("rejects update config from non admin", async () => {
+    try {
+      await program.methods
+        .updateConfig(null, null, null, 0, null)
+        .accountsPartial({
+          admin: user.publicKey,
+          config: config,
+        })
+        .signers([user])
+        .rpc();
+      throw new Error("update config should have failed");
+    } catch (error) {
+      if (!error.toString().includes("InvalidAdmin")) {
+        throw error;
+      }
+    }
+  });
+
+  it("lets the admin configure lock tier lengths and multipliers", async () => {
+    const day = 86400;
+    const lockTiers = [
+      { duration: 7 * day, multiplierBps: 10_000 },
+      { duration: 30 * day, multiplierBps: 12_500 },
+      { duration: 90 * day, multiplierBps: 15_000 },
+      { duration: 400 * day, multiplierBps: 25_000 },
+    ];
+    await program.methods
+      .setLockTiers(lockTiers)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    const configAccount = await program.account.stakeConfigAccount.fetch(config);
+    const year = configAccount.lockTiers[3];
+    if (year.duration !== 400 * day || year.multiplierBps !== 25_000) {
+      throw new Error("year tier should use the configured length and multiplier");
+    }
+
+    try {
+      await program.methods
+        .setLockTiers([{ duration: 0, multiplierBps: 9_000 }, ...lockTiers.slice(1)])
+        .accountsPartial({ admin: admin.publicKey, config })
+        .signers([admin])
+        .rpc();
+      throw new Error("a tier below 1x should have been rejected");
+    } catch (error) {
+      if (!error.toString().includes("InvalidLockTiers")) {
+        throw error;
+      }
+    }
+  });
+
+  it("transfers admin authority in two steps", async () => {
+    await program.methods
+      .proposeAdmin(user.publicKey)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .cancelAdminTransfer()
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .proposeAdmin(user.publicKey)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .acceptAdmin()
+      .accountsPartial({ newAdmin: user.publicKey, config })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+
+    // hand authority back so the remaining tests can keep using admin
+    await program.methods
+      .proposeAdmin(admin.publicKey)
+      .accountsPartial({ admin: user.publicKey, config })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .acceptAdmin()
+      .accountsPartial({ newAdmin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    const configAccount = await program.account.stakeConfigAccount.fetch(
+      config
+    );
+    console.log("Config admin after transfer:", configAccount.admin.toBase58());
+  });
+
+  it("creates a permissionless pool and charges the protocol fee", async () => {
+    const [protocolConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol")],
+      programId
+    );
+    const [treasury] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury")],
+      programId
+    );
+    const poolCreationFee = LAMPORTS_PER_SOL / 10;
+
+    await program.methods
+      .initializeProtocol(new anchor.BN(poolCreationFee))
+      .accountsPartial({ admin: admin.publicKey, protocolConfig, treasury })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    const communityPoolId = (
+      await program.account.protocolConfigAccount.fetch(protocolConfig)
+    ).nextPoolId;
+    const [communityConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), communityPoolId.toArrayLike(Buffer, "le", 8)],
+      programId
+    );
+    const [communityRewardMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rewards"), communityConfig.toBuffer()],
+      programId
+    );
+
+    const treasuryBefore = await connection.getBalance(treasury);
+    await program.methods
+      .createPool({
+        pointsPerNftStake: 1,
+        pointsPerSplStake: 1,
+        pointsPerSolStake: 1,
+        minFreezePeriod: 60,
+        emissionPerSecond: new BN(100_000),
+        schedule: {
+          startTime: new BN(0),
+          endTime: new BN(0),
+          halvingInterval: new BN(0),
+        },
+        maxRewardSupply: new BN("1000000000000"),
+        rewardBudget: new BN("1000000000000"),
+      })
+      .accountsPartial({
+        creator: user.publicKey,
+        protocolConfig,
+        treasury,
+        config: communityConfig,
+        rewardMint: communityRewardMint,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+    const treasuryAfter = await connection.getBalance(treasury);
+
+    const communityConfigAccount =
+      await program.account.stakeConfigAccount.fetch(communityConfig);
+    if (!communityConfigAccount.admin.equals(user.publicKey)) {
+      throw new Error("pool creator should be the pool admin");
+    }
+    if (!communityConfigAccount.poolId.eq(communityPoolId)) {
+      throw new Error("pool id should be assigned by the protocol counter");
+    }
+    if (treasuryAfter - treasuryBefore !== poolCreationFee) {
+      throw new Error("protocol fee was not paid into the treasury");
+    }
+    const protocolAccount =
+      await program.account.protocolConfigAccount.fetch(protocolConfig);
+    if (!protocolAccount.nextPoolId.eq(communityPoolId.addn(1))) {
+      throw new Error("pool id counter should advance after create_pool");
+    }
+  });
+
+  it("transfers protocol admin authority in two steps", async () => {
+    const [protocolConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol")],
+      programId
+    );
+
+    await program.methods
+      .proposeProtocolAdmin(user.publicKey)
+      .accountsPartial({ admin: admin.publicKey, protocolConfig })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .cancelProtocolAdminTransfer()
+      .accountsPartial({ admin: admin.publicKey, protocolConfig })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    try {
+      await program.methods
+        .acceptProtocolAdmin()
+        .accountsPartial({ newAdmin: user.publicKey, protocolConfig })
+        .signers([user])
+        .rpc();
+      throw new Error("a cancelled transfer should not be accepted");
+    } catch (error) {
+      if (!error.toString().includes("NoPendingAdmin")) {
+        throw error;
+      }
+    }
+
+    await program.methods
+      .proposeProtocolAdmin(user.publicKey)
+      .accountsPartial({ admin: admin.publicKey, protocolConfig })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .acceptProtocolAdmin()
+      .accountsPartial({ newAdmin: user.publicKey, protocolConfig })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+
+    let protocolAccount = await program.account.protocolConfigAccount.fetch(protocolConfig);
+    if (!protocolAccount.admin.equals(user.publicKey) || protocolAccount.pendingAdmin !== null) {
+      throw new Error("the accepted key should become the protocol admin");
+    }
+
+    // hand authority back so the remaining tests can keep using admin
+    await program.methods
+      .proposeProtocolAdmin(admin.publicKey)
+      .accountsPartial({ admin: user.publicKey, protocolConfig })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .acceptProtocolAdmin()
+      .accountsPartial({ newAdmin: admin.publicKey, protocolConfig })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    protocolAccount = await program.account.protocolConfigAccount.fetch(protocolConfig);
+    if (!protocolAccount.admin.equals(admin.publicKey)) {
+      throw new Error("protocol admin should be handed back");
+    }
+  });
+
+  it("rejects an admin pool id in the permissionless range", async () => {
+    const reservedPoolId = new BN(2).pow(new BN(40));
+    const [reservedConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), reservedPoolId.toArrayLike(Buffer, "le", 8)],
+      programId
+    );
+    const [reservedRewardMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rewards"), reservedConfig.toBuffer()],
+      programId
+    );
+    try {
+      await program.methods
+        .initializeConfig(reservedPoolId, {
+          pointsPerNftStake: 1,
+          pointsPerSplStake: 1,
+          pointsPerSolStake: 1,
+          minFreezePeriod: 60,
+          emissionPerSecond: new BN(0),
+          schedule: {
+            startTime: new BN(0),
+            endTime: new BN(0),
+            halvingInterval: new BN(0),
+          },
+          maxRewardSupply: new BN(0),
+          rewardBudget: new BN(0),
+        })
+        .accountsPartial({
+          admin: admin.publicKey,
+          config: reservedConfig,
+          rewardMint: reservedRewardMint,
+        })
+        .signers([admin])
+        .rpc();
+      throw new Error("reserved pool id should have been rejected");
+    } catch (error) {
+      if (!error.toString().includes("InvalidPoolId")) {
+        throw error;
+      }
+    }
+  });
+
+  it("pays rewards out of a funded treasury for an existing token", async () => {
+    const [protocolConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol")],
+      programId
+    );
+    const [treasury] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury")],
+      programId
+    );
+    const partnerMint = await createMint(
+      connection,
+      admin,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    const partnerPoolId = (
+      await program.account.protocolConfigAccount.fetch(protocolConfig)
+    ).nextPoolId;
+    const [partnerConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), partnerPoolId.toArrayLike(Buffer, "le", 8)],
+      programId
+    );
+    const rewardTreasury = getAssociatedTokenAddressSync(
+      partnerMint,
+      partnerConfig,
+      true
+    );
+
+    await program.methods
+      .createTreasuryPool({
+        pointsPerNftStake: 1,
+        pointsPerSplStake: 1,
+        pointsPerSolStake: 1,
+        minFreezePeriod: 60,
+        emissionPerSecond: new BN(100_000),
+        schedule: {
+          startTime: new BN(0),
+          endTime: new BN(0),
+          halvingInterval: new BN(0),
+        },
+        maxRewardSupply: new BN("1000000000000"),
+        rewardBudget: new BN(0),
+      })
+      .accountsPartial({
+        creator: user.publicKey,
+        protocolConfig,
+        treasury,
+        config: partnerConfig,
+        rewardMint: partnerMint,
+        rewardTreasury,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+
+    const adminPartnerAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin,
+      partnerMint,
+      admin.publicKey
+    );
+    await mintTo(
+      connection,
+      admin,
+      partnerMint,
+      adminPartnerAta.address,
+      admin,
+      5_000_000
+    );
+
+    await program.methods
+      .fundRewards(new BN(5_000_000))
+      .accountsPartial({
+        funder: admin.publicKey,
+        config: partnerConfig,
+        rewardMint: partnerMint,
+        funderAta: adminPartnerAta.address,
+        rewardTreasury,
+      })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    const userPartnerAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      user,
+      partnerMint,
+      user.publicKey
+    );
+    await program.methods
+      .withdrawUnallocatedRewards(new BN(2_000_000))
+      .accountsPartial({
+        admin: user.publicKey,
+        config: partnerConfig,
+        rewardMint: partnerMint,
+        adminAta: userPartnerAta.address,
+        rewardTreasury,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+
+    const partnerConfigAccount =
+      await program.account.stakeConfigAccount.fetch(partnerConfig);
+    if (partnerConfigAccount.rewardBudget.toNumber() !== 3_000_000) {
+      throw new Error("treasury budget should track deposits and withdrawals");
+    }
+    treasuryPoolConfig = partnerConfig;
+    treasuryPoolMint = partnerMint;
+  });
+
+  it("manages the collection allowlist", async () => {
+    const collectionMint = await createMint(
+      connection,
+      admin,
+      admin.publicKey,
+      null,
+      0
+    );
+    const [allowedCollection] = PublicKey.findProgramAddressSync(
+      [Buffer.from("collection"), config.toBuffer(), collectionMint.toBuffer()],
+      programId
+    );
+
+    await program.methods
+      .addCollection(20, 7 * 86400)
+      .accountsPartial({
+        admin: admin.publicKey,
+        collectionMint,
+        config,
+        allowedCollection,
+      })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .updateCollection(null, 86400)
+      .accountsPartial({ admin: admin.publicKey, config, allowedCollection })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .setCollectionEnabled(false)
+      .accountsPartial({ admin: admin.publicKey, config, allowedCollection })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    const entry = await program.account.allowedCollection.fetch(
+      allowedCollection
+    );
+    if (entry.enabled) {
+      throw new Error("collection should be disabled");
+    }
+
+    await program.methods
+      .removeCollection()
+      .accountsPartial({ admin: admin.publicKey, config, allowedCollection })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+  });
+
+  it("registers and funds a partner reward token", async () => {
+    partnerRewardMint = await createMint(
+      connection,
+      admin,
+      admin.publicKey,
+      null,
+      6
+    );
+    const adminPartnerAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin,
+      partnerRewardMint,
+      admin.publicKey
+    );
+    partnerRewardTreasury = (
+      await getOrCreateAssociatedTokenAccount(
+        connection,
+        admin,
+        partnerRewardMint,
+        config,
+        true
+      )
+    ).address;
+    await mintTo(
+      connection,
+      admin,
+      partnerRewardMint,
+      adminPartnerAta.address,
+      admin,
+      10_000_000_000
+    );
+
+    await program.methods
+      .addRewardToken({ treasury: {} }, new BN(1_000), new BN(10_000_000_000), new BN(0))
+      .accountsPartial({
+        admin: admin.publicKey,
+        config,
+        rewardMint: partnerRewardMint,
+      })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .fundRewardToken(0, new BN(10_000_000_000))
+      .accountsPartial({
+        funder: admin.publicKey,
+        config,
+        rewardMint: partnerRewardMint,
+        funderAta: adminPartnerAta.address,
+        rewardTreasury: partnerRewardTreasury,
+      })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .withdrawUnallocatedRewardToken(0, new BN(1_000_000_000))
+      .accountsPartial({
+        admin: admin.publicKey,
+        config,
+        rewardMint: partnerRewardMint,
+        adminAta: adminPartnerAta.address,
+        rewardTreasury: partnerRewardTreasury,
+      })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+    try {
+      await program.methods
+        .withdrawUnallocatedRewardToken(0, new BN(10_000_000_000))
+        .accountsPartial({
+          admin: admin.publicKey,
+          config,
+          rewardMint: partnerRewardMint,
+          adminAta: adminPartnerAta.address,
+          rewardTreasury: partnerRewardTreasury,
+        })
+        .signers([admin])
+        .rpc();
+      throw new Error("withdrawing past the partner budget should have failed");
+    } catch (error) {
+      if (!error.toString().includes("InsufficientFunds")) {
+        throw error;
+      }
+    }
+
+    // registered but never funded, settlement has to get past its empty budget
+    const unfundedRewardMint = await createMint(
+      connection,
+      admin,
+      admin.publicKey,
+      null,
+      6
+    );
+    await program.methods
+      .addRewardToken({ treasury: {} }, new BN(1_000), new BN(10_000_000_000), new BN(0))
+      .accountsPartial({
+        admin: admin.publicKey,
+        config,
+        rewardMint: unfundedRewardMint,
+      })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    const configAccount = await program.account.stakeConfigAccount.fetch(config);
+    if (configAccount.extraRewards[0].rewardBudget.toNumber() !== 9_000_000_000) {
+      throw new Error("partner reward budget should track deposits and withdrawals");
+    }
+  });
+
+  it("tops up a minted extra reward token within its supply cap", async () => {
+    const extraPoolId = new BN(randomBytes(4));
+    const [extraConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), extraPoolId.toArrayLike(Buffer, "le", 8)],
+      programId
+    );
+    const [extraPoolMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rewards"), extraConfig.toBuffer()],
+      programId
+    );
+    await program.methods
+      .initializeConfig(extraPoolId, {
+        pointsPerNftStake: 1,
+        pointsPerSplStake: 1,
+        pointsPerSolStake: 1,
+        minFreezePeriod: 0,
+        emissionPerSecond: new BN(1_000),
+        schedule: {
+          startTime: new BN(0),
+          endTime: new BN(0),
+          halvingInterval: new BN(0),
+        },
+        maxRewardSupply: new BN(1_000_000),
+        rewardBudget: new BN(1_000_000),
+      })
+      .accountsPartial({
+        admin: admin.publicKey,
+        config: extraConfig,
+        rewardMint: extraPoolMint,
+      })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    // the pool mints the partner token itself, so its config has to hold the mint authority
+    const mintedRewardMint = await createMint(connection, admin, admin.publicKey, null, 6);
+    await setAuthority(
+      connection,
+      admin,
+      mintedRewardMint,
+      admin,
+      AuthorityType.MintTokens,
+      extraConfig
+    );
+
+    try {
+      await program.methods
+        .addRewardToken({ mint: {} }, new BN(1_000), new BN(1_000_000), new BN(2_000_000))
+        .accountsPartial({
+          admin: admin.publicKey,
+          config: extraConfig,
+          rewardMint: mintedRewardMint,
+        })
+        .signers([admin])
+        .rpc();
+      throw new Error("a budget above the token supply cap should be rejected");
+    } catch (error) {
+      if (!error.toString().includes("RewardSupplyCapExceeded")) {
+        throw error;
+      }
+    }
+
+    await program.methods
+      .addRewardToken({ mint: {} }, new BN(1_000), new BN(1_000_000), new BN(600_000))
+      .accountsPartial({
+        admin: admin.publicKey,
+        config: extraConfig,
+        rewardMint: mintedRewardMint,
+      })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .topUpRewardTokenBudget(0, new BN(400_000))
+      .accountsPartial({ admin: admin.publicKey, config: extraConfig })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+    const extra = (await program.account.stakeConfigAccount.fetch(extraConfig)).extraRewards[0];
+    if (!extra.rewardBudget.eq(new BN(1_000_000)) || !extra.maxRewardSupply.eq(new BN(1_000_000))) {
+      throw new Error("the top up should grow the token budget up to its cap");
+    }
+
+    try {
+      await program.methods
+        .topUpRewardTokenBudget(0, new BN(1))
+        .accountsPartial({ admin: admin.publicKey, config: extraConfig })
+        .signers([admin])
+        .rpc();
+      throw new Error("a top up past the token supply cap should fail");
+    } catch (error) {
+      if (!error.toString().includes("RewardSupplyCapExceeded")) {
+        throw error;
+      }
+    }
+  });
+
+  it("initialize user", async () => {
+    const txSig = await program.methods
+      .initializeUser()
+      .accountsPartial({
+        user: user.publicKey,
+        userAccount,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+    await confirm(txSig);
+    await log(txSig);
+  });
+
+  it("leaves accounts already in the current layout alone", async () => {
+    const userAcc = await program.account.userAccount.fetch(userAccount);
+    if (userAcc.version !== 1) {
+      throw new Error("new user accounts should start at the current version");
+    }
+
+    try {
+      await program.methods
+        .migrateUser()
+        .accountsPartial({ user: user.publicKey, userAccount })
+        .signers([user])
+        .rpc();
+      throw new Error("migrating a current account should fail");
+    } catch (error) {
+      if (!error.toString().includes("AccountAlreadyMigrated")) {
+        throw error;
+      }
+    }
+  });
+
+  it("migrates the legacy pool and a sol position staked in it", async () => {
+    // tests/fixtures holds accounts as the program wrote them before pools existed: the
+    // config at [b"config"] with its reward mint, a 1 SOL position of the admin and one
+    // it exited, which legacy unstake_sol emptied but neither closed nor took off its total
+    const [legacyConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      programId
+    );
+    const [legacyRewardMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rewards"), legacyConfig.toBuffer()],
+      programId
+    );
+    const [legacyStake] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        legacyConfig.toBuffer(),
+        admin.publicKey.toBuffer(),
+        new BN(42).toArrayLike(Buffer, "le", 8),
+      ],
+      programId
+    );
+    const [legacyVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), legacyStake.toBuffer()],
+      programId
+    );
+    const [exitedStake] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        legacyConfig.toBuffer(),
+        admin.publicKey.toBuffer(),
+        new BN(43).toArrayLike(Buffer, "le", 8),
+      ],
+      programId
+    );
+    const [exitedVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), exitedStake.toBuffer()],
+      programId
+    );
+    const [adminUserAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), admin.publicKey.toBuffer()],
+      programId
+    );
+
+    await program.methods
+      .migrateConfig()
+      .accountsPartial({
+        admin: admin.publicKey,
+        config: legacyConfig,
+        rewardMint: legacyRewardMint,
+      })
+      .rpc()
+      .then(confirm);
+    const migratedConfig = await program.account.stakeConfigAccount.fetch(legacyConfig);
+    if (
+      !migratedConfig.admin.equals(admin.publicKey) ||
+      !migratedConfig.poolId.eq(new BN("18446744073709551615"))
+    ) {
+      throw new Error("legacy pool should come over under the admin with the legacy pool id");
+    }
+    if (
+      migratedConfig.pointsPerSolStake !== 2 ||
+      migratedConfig.minFreezePeriod !== 86400 ||
+      !migratedConfig.rewardMint.equals(legacyRewardMint)
+    ) {
+      throw new Error("legacy pool should keep its rates and reward mint");
+    }
+    if (!migratedConfig.rewardsPaid.eq(new BN(2_000_000_000))) {
+      throw new Error("what the legacy pool minted should count as paid");
+    }
+
+    await program.methods
+      .migrateUser()
+      .accountsPartial({ user: admin.publicKey, userAccount: adminUserAccount })
+      .rpc()
+      .then(confirm);
+    const migratedUser = await program.account.userAccount.fetch(adminUserAccount);
+    if (!migratedUser.points.eq(new BN(2_000_000_000)) || !migratedUser.solStakedAmount.isZero()) {
+      throw new Error("migrated user should keep its points and rebuild its totals from positions");
+    }
+
+    // the exited position has nothing left to migrate, it is closed and its rent refunded
+    const exitedRent = (await connection.getAccountInfo(exitedStake)).lamports;
+    const adminBefore = await connection.getBalance(admin.publicKey);
+    await program.methods
+      .migrateStake()
+      .accountsPartial({
+        user: admin.publicKey,
+        stakeAccount: exitedStake,
+        config: legacyConfig,
+        vault: exitedVault,
+        allowedSplMint: null,
+        metadata: null,
+        allowedCollection: null,
+        userAccount: adminUserAccount,
+      })
+      .rpc()
+      .then(confirm);
+    if ((await connection.getAccountInfo(exitedStake)) !== null) {
+      throw new Error("an exited legacy position should be closed");
+    }
+    if ((await connection.getBalance(admin.publicKey)) - adminBefore + 10_000 < exitedRent) {
+      throw new Error("closing an exited legacy position should refund its rent");
+    }
+
+    await program.methods
+      .migrateStake()
+      .accountsPartial({
+        user: admin.publicKey,
+        stakeAccount: legacyStake,
+        config: legacyConfig,
+        vault: legacyVault,
+        allowedSplMint: null,
+        metadata: null,
+        allowedCollection: null,
+        userAccount: adminUserAccount,
+      })
+      .rpc()
+      .then(confirm);
+    const userAfterStake = await program.account.userAccount.fetch(adminUserAccount);
+    if (userAfterStake.solStakedAmount.toNumber() !== LAMPORTS_PER_SOL) {
+      throw new Error("migrated positions should add their principal to the user totals");
+    }
+    const position = await program.account.stakeAccount.fetch(legacyStake);
+    if (!position.kind.sol || position.amount.toNumber() !== LAMPORTS_PER_SOL) {
+      throw new Error("kind and principal should come from the position address and vault");
+    }
+    if (position.multiplierBps !== 10_000 || position.unlockAt.toNumber() !== 1_700_000_000 + 86400) {
+      throw new Error("legacy position should stay at 1x behind the pool freeze period");
+    }
+    const poolAfter = await program.account.stakeConfigAccount.fetch(legacyConfig);
+    // 1 SOL at 2 points per SOL on the 6 decimal reward mint
+    if (!position.weight.eq(new BN(2_000_000)) || !poolAfter.totalStakedWeight.eq(position.weight)) {
+      throw new Error("legacy position should join the pool with its sol weight");
+    }
+
+    // the migrated position leaves through the current instructions at its legacy addresses
+    const adminRewardAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin,
+      legacyRewardMint,
+      admin.publicKey
+    );
+    await program.methods
+      .unstakeSol(new BN(LAMPORTS_PER_SOL))
+      .accountsPartial({
+        user: admin.publicKey,
+        mint: legacyRewardMint,
+        rewardMint: legacyRewardMint,
+        userRewardAta: adminRewardAta.address,
+        rewardTreasury: null,
+        stakeAccount: legacyStake,
+        config: legacyConfig,
+        vault: legacyVault,
+        userAccount: adminUserAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc()
+      .then(confirm);
+    if ((await connection.getAccountInfo(legacyStake)) !== null) {
+      throw new Error("fully unstaked legacy position should be closed");
+    }
+    const adminUser = await program.account.userAccount.fetch(adminUserAccount);
+    if (adminUser.solStakedAmount.toNumber() !== 0) {
+      throw new Error("migrated user totals should follow the unstake");
+    }
+  });
+
+  it("stake sol", async () => {
+    const stakeAmount = 1 * LAMPORTS_PER_SOL;
+    const txSig = await program.methods
+      .stakeSol(seed, new anchor.BN(stakeAmount), { week: {} })
+      .accountsPartial({
+        user: user.publicKey,
+        config: config,
+        stakeAccount,
+        userAccount,
+        vault,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+    await confirm(txSig);
+    await log(txSig);
+
+    const position = await program.account.stakeAccount.fetch(stakeAccount);
+    if (!position.kind.sol || position.amount.toNumber() !== stakeAmount) {
+      throw new Error("position should record its kind and principal");
+    }
+    if (position.multiplierBps !== 10_000) {
+      throw new Error("week tier should stake at 1x");
+    }
+    if (position.unlockAt.sub(position.stakedAt).toNumber() !== 7 * 86400) {
+      throw new Error("week tier should unlock after seven days");
+    }
+  });
+
+  it("keeps rewards accrued to stakers out of an unallocated withdrawal", async () => {
+    const poolSeed = new BN(randomBytes(8));
+    const [poolStake] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        treasuryPoolConfig.toBuffer(),
+        user.publicKey.toBuffer(),
+        poolSeed.toArrayLike(Buffer, "le", 8),
+      ],
+      programId
+    );
+    const [poolVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), poolStake.toBuffer()],
+      programId
+    );
+    const [poolPenaltyVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("penalty"), treasuryPoolConfig.toBuffer()],
+      programId
+    );
+    const rewardTreasury = getAssociatedTokenAddressSync(
+      treasuryPoolMint,
+      treasuryPoolConfig,
+      true
+    );
+    const userPoolAta = getAssociatedTokenAddressSync(
+      treasuryPoolMint,
+      user.publicKey
+    );
+
+    await program.methods
+      .stakeSol(poolSeed, new anchor.BN(LAMPORTS_PER_SOL / 10), { week: {} })
+      .accountsPartial({
+        user: user.publicKey,
+        config: treasuryPoolConfig,
+        stakeAccount: poolStake,
+        userAccount,
+        vault: poolVault,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const budget = (
+      await program.account.stakeConfigAccount.fetch(treasuryPoolConfig)
+    ).rewardBudget;
+    try {
+      await program.methods
+        .withdrawUnallocatedRewards(budget)
+        .accountsPartial({
+          admin: user.publicKey,
+          config: treasuryPoolConfig,
+          rewardMint: treasuryPoolMint,
+          adminAta: userPoolAta,
+          rewardTreasury,
+        })
+        .signers([user])
+        .rpc();
+      throw new Error("accrued rewards should not be withdrawable");
+    } catch (error) {
+      if (!error.toString().includes("InsufficientFunds")) {
+        throw error;
+      }
+    }
+
+    // forfeiting the position frees what it had accrued again
+    await program.methods
+      .earlyUnstakeSol()
+      .accountsPartial({
+        user: user.publicKey,
+        stakeAccount: poolStake,
+        config: treasuryPoolConfig,
+        vault: poolVault,
+        penaltyVault: poolPenaltyVault,
+        userAccount,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+    const poolConfig = await program.account.stakeConfigAccount.fetch(
+      treasuryPoolConfig
+    );
+    if (poolConfig.accruedRewards.toNumber() > 10) {
+      throw new Error("a forfeited position should release its accrued rewards");
+    }
+    await program.methods
+      .withdrawUnallocatedRewards(
+        poolConfig.rewardBudget.sub(poolConfig.accruedRewards)
+      )
+      .accountsPartial({
+        admin: user.publicKey,
+        config: treasuryPoolConfig,
+        rewardMint: treasuryPoolMint,
+        adminAta: userPoolAta,
+        rewardTreasury,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+  });
+
+  it("keeps rewards accrued before a rate change", async () => {
+    const ratePoolId = new BN(randomBytes(4));
+    const [rateConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), ratePoolId.toArrayLike(Buffer, "le", 8)],
+      programId
+    );
+    const [rateRewardMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rewards"), rateConfig.toBuffer()],
+      programId
+    );
+    const rateSeed = new BN(randomBytes(8));
+    const [rateStake] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        rateConfig.toBuffer(),
+        user.publicKey.toBuffer(),
+        rateSeed.toArrayLike(Buffer, "le", 8),
+      ],
+      programId
+    );
+    const [rateVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), rateStake.toBuffer()],
+      programId
+    );
+
+    await program.methods
+      .initializeConfig(ratePoolId, {
+        pointsPerNftStake: 1,
+        pointsPerSplStake: 1,
+        pointsPerSolStake: 1,
+        minFreezePeriod: 0,
+        emissionPerSecond: new BN(1_000_000),
+        schedule: {
+          startTime: new BN(0),
+          endTime: new BN(0),
+          halvingInterval: new BN(0),
+        },
+        maxRewardSupply: new BN("1000000000000"),
+        rewardBudget: new BN("1000000000000"),
+      })
+      .accountsPartial({
+        admin: admin.publicKey,
+        config: rateConfig,
+        rewardMint: rateRewardMint,
+      })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .stakeSol(rateSeed, new anchor.BN(LAMPORTS_PER_SOL / 10), { week: {} })
+      .accountsPartial({
+        user: user.publicKey,
+        config: rateConfig,
+        stakeAccount: rateStake,
+        userAccount,
+        vault: rateVault,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    // stopping the emission checkpoints what the position earned at the old rate
+    await program.methods
+      .updateConfig(null, null, null, null, new BN(0))
+      .accountsPartial({ admin: admin.publicKey, config: rateConfig })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+    const { accRewardPerShare } = await program.account.stakeConfigAccount.fetch(rateConfig);
+    const position = await program.account.stakeAccount.fetch(rateStake);
+    const earned = position.weight
+      .mul(accRewardPerShare)
+      .div(new BN("1000000000000"))
+      .sub(position.rewardDebt);
+    if (earned.isZero()) {
+      throw new Error("the position should have accrued before the rate change");
+    }
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const userRateAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      user,
+      rateRewardMint,
+      user.publicKey
+    );
+    await program.methods
+      .claimRewards()
+      .accountsPartial({
+        user: user.publicKey,
+        rewardMint: rateRewardMint,
+        userRewardAta: userRateAta.address,
+        rewardTreasury: null,
+        stakeAccount: rateStake,
+        config: rateConfig,
+        userAccount,
+        allowedCollection: null,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+    const paid = new BN(
+      (await connection.getTokenAccountBalance(userRateAta.address)).value.amount
+    );
+    if (!paid.eq(earned)) {
+      throw new Error("the rate change should not touch what was earned before it");
+    }
+  });
+
+  it("rejects a top-up its reward budget cannot pay out", async () => {
+    const shortPoolId = new BN(randomBytes(4));
+    const [shortConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), shortPoolId.toArrayLike(Buffer, "le", 8)],
+      programId
+    );
+    const [shortRewardMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rewards"), shortConfig.toBuffer()],
+      programId
+    );
+    const shortSeed = new BN(randomBytes(8));
+    const [shortStake] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        shortConfig.toBuffer(),
+        user.publicKey.toBuffer(),
+        shortSeed.toArrayLike(Buffer, "le", 8),
+      ],
+      programId
+    );
+    const [shortVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), shortStake.toBuffer()],
+      programId
+    );
+
+    await program.methods
+      .initializeConfig(shortPoolId, {
+        pointsPerNftStake: 1,
+        pointsPerSplStake: 1,
+        pointsPerSolStake: 1,
+        minFreezePeriod: 0,
+        emissionPerSecond: new BN(1_000_000),
+        schedule: {
+          startTime: new BN(0),
+          endTime: new BN(0),
+          halvingInterval: new BN(0),
+        },
+        maxRewardSupply: new BN("1000000000000"),
+        rewardBudget: new BN(1),
+      })
+      .accountsPartial({
+        admin: admin.publicKey,
+        config: shortConfig,
+        rewardMint: shortRewardMint,
+      })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await program.methods
+      .stakeSol(shortSeed, new anchor.BN(LAMPORTS_PER_SOL / 10), { week: {} })
+      .accountsPartial({
+        user: user.publicKey,
+        config: shortConfig,
+        stakeAccount: shortStake,
+        userAccount,
+        vault: shortVault,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const userShortAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      user,
+      shortRewardMint,
+      user.publicKey
+    );
+    const topUp = () =>
+      program.methods
+        .increaseStakeSol(new BN(LAMPORTS_PER_SOL / 10))
+        .accountsPartial({
+          user: user.publicKey,
+          rewardMint: shortRewardMint,
+          userRewardAta: userShortAta.address,
+          rewardTreasury: null,
+          stakeAccount: shortStake,
+          config: shortConfig,
+          vault: shortVault,
+          userAccount,
+        })
+        .signers([user])
+        .rpc();
+    try {
+      await topUp();
+      throw new Error("a top-up should not forfeit rewards the budget cannot cover");
+    } catch (error) {
+      if (!error.toString().includes("RewardBudgetExhausted")) {
+        throw error;
+      }
+    }
+
+    await program.methods
+      .topUpRewardBudget(new BN("100000000000"))
+      .accountsPartial({ admin: admin.publicKey, config: shortConfig })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+    await topUp().then(confirm);
+    const paid = new BN(
+      (await connection.getTokenAccountBalance(userShortAta.address)).value.amount
+    );
+    if (paid.isZero()) {
+      throw new Error("the top-up should pay out what the position earned");
+    }
+  });
+
+  it("stake spl", async () => {
+    // Create a new SPL mint
+    splMint = await createMint(
+      connection,
+      admin,
+      admin.publicKey,
+      null,
+      6 // decimals
+    );
+
+    // Create user's ATA for the SPL mint
+    const userSplAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      user,
+      splMint,
+      user.publicKey
+    );
+
+    // Mint SPL tokens to user
+    await mintTo(
+      connection,
+      admin,
+      splMint,
+      userSplAta.address,
+      admin,
+      1_000_000 // 1 token (with 6 decimals)
+    );
+
+    [allowedSplMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("spl_mint"), config.toBuffer(), splMint.toBuffer()],
+      programId
+    );
+
+    await program.methods
+      .addSplMint(10_000)
+      .accountsPartial({
+        admin: admin.publicKey,
+        mint: splMint,
+        config,
+        allowedSplMint,
+      })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    // Derive stake_account PDA for SPL
+    const splSeed = new BN(12345);
+    [splStakeAccount] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        config.toBuffer(),
+        user.publicKey.toBuffer(),
+        splMint.toBuffer(),
+        splSeed.toArrayLike(Buffer, "le", 8),
+      ],
+      programId
+    );
+
+    // Derive vault_ata PDA for SPL
+    vaultAta = getAssociatedTokenAddressSync(
+      splMint,
+      splStakeAccount,
+      true
+    );
+
+    const stakeAmount = 500_000; // 0.5 SPL token
+    const txSig = await program.methods
+      .stakeSpl(splSeed, new anchor.BN(stakeAmount), { month: {} })
+      .accountsPartial({
+        user: user.publicKey,
+        mint: splMint,
+        allowedSplMint,
+        mintAta: userSplAta.address,
+        stakeAccount: splStakeAccount,
+        config: config,
+        vaultAta: vaultAta,
+        userAccount: userAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+    await confirm(txSig);
+    await log(txSig);
+
+    // a second position on the same mint under another seed, locked for longer
+    const ladderSeed = new BN(67890);
+    const [ladderStakeAccount] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        config.toBuffer(),
+        user.publicKey.toBuffer(),
+        splMint.toBuffer(),
+        ladderSeed.toArrayLike(Buffer, "le", 8),
+      ],
+      programId
+    );
+    await program.methods
+      .stakeSpl(ladderSeed, new anchor.BN(200_000), { year: {} })
+      .accountsPartial({
+        user: user.publicKey,
+        mint: splMint,
+        allowedSplMint,
+        mintAta: userSplAta.address,
+        stakeAccount: ladderStakeAccount,
+        config,
+        vaultAta: getAssociatedTokenAddressSync(splMint, ladderStakeAccount, true),
+        userAccount,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+    console.log("SPL staked successfully!");
+    const userAcc = await program.account.userAccount.fetch(userAccount);
+    console.log("User account after SPL stake:", userAcc);
+  });
+
+  it("blocks sol staking while paused", async () => {
+    const PAUSE_STAKE_SOL = 1 << 2;
+    await program.methods
+      .setPaused(PAUSE_STAKE_SOL)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    const pausedSeed = new BN(randomBytes(8));
+    const [pausedStake] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        config.toBuffer(),
+        user.publicKey.toBuffer(),
+        pausedSeed.toArrayLike(Buffer, "le", 8),
+      ],
+      programId
+    );
+    const [pausedVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), pausedStake.toBuffer()],
+      programId
+    );
+    try {
+      await program.methods
+        .stakeSol(pausedSeed, new anchor.BN(LAMPORTS_PER_SOL / 10), { week: {} })
+        .accountsPartial({
+          user: user.publicKey,
+          config,
+          stakeAccount: pausedStake,
+          userAccount,
+          vault: pausedVault,
+        })
+        .signers([user])
+        .rpc();
+      throw new Error("stake sol should have been paused");
+    } catch (error) {
+      if (!error.toString().includes("StakeSolPaused")) {
+        throw error;
+      }
+    }
+
+    await program.methods
+      .setPaused(0)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+  });
+
+  it("rejects an empty sol stake", async () => {
+    const emptySeed = new BN(randomBytes(8));
+    const [emptyStake] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        config.toBuffer(),
+        user.publicKey.toBuffer(),
+        emptySeed.toArrayLike(Buffer, "le", 8),
+      ],
+      programId
+    );
+    const [emptyVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), emptyStake.toBuffer()],
+      programId
+    );
+    try {
+      await program.methods
+        .stakeSol(emptySeed, new anchor.BN(0), { week: {} })
+        .accountsPartial({
+          user: user.publicKey,
+          config,
+          stakeAccount: emptyStake,
+          userAccount,
+          vault: emptyVault,
+        })
+        .signers([user])
+        .rpc();
+      throw new Error("a zero amount stake should be rejected");
+    } catch (error) {
+      if (!error.toString().includes("InvalidStakeAmount")) {
+        throw error;
+      }
+    }
+  });
+
+  it("exits a locked sol position early and pays the penalty", async () => {
+    const [penaltyVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("penalty"), config.toBuffer()],
       programId
     );
+    await program.methods
+      .setEarlyUnstakePenalty(1_000)
+      .accountsPartial({ admin: admin.publicKey, config, penaltyVault })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
 
-    [stakeAccount] = PublicKey.findProgramAddressSync(
+    const earlySeed = new BN(randomBytes(8));
+    const [earlyStake] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("stake"),
         config.toBuffer(),
         user.publicKey.toBuffer(),
-        seed.toArrayLike(Buffer, "le", 8)
+        earlySeed.toArrayLike(Buffer, "le", 8),
       ],
       programId
     );
-
-    [vault] = PublicKey.findProgramAddressSync(
-      [Buffer.from("vault"), stakeAccount.toBuffer()],
+    const [earlyVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), earlyStake.toBuffer()],
       programId
     );
+    const stakeAmount = LAMPORTS_PER_SOL / 10;
+    await program.methods
+      .stakeSol(earlySeed, new anchor.BN(stakeAmount), { quarter: {} })
+      .accountsPartial({
+        user: user.publicKey,
+        config,
+        stakeAccount: earlyStake,
+        userAccount,
+        vault: earlyVault,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
 
-    [rewardMintPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("rewards"), config.toBuffer()],
-      programId
-    );
+    const penaltyBefore = await connection.getBalance(penaltyVault);
+    await program.methods
+      .earlyUnstakeSol()
+      .accountsPartial({
+        user: user.publicKey,
+        stakeAccount: earlyStake,
+        config,
+        vault: earlyVault,
+        penaltyVault,
+        userAccount,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+    const penaltyAfter = await connection.getBalance(penaltyVault);
+
+    if (penaltyAfter - penaltyBefore !== stakeAmount / 10) {
+      throw new Error("a 10% penalty should be kept by the penalty vault");
+    }
   });
 
-  it("initialized config", async () => {
+  it("claims rewards on an open sol position", async () => {
+    const userRewardAtaAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      user,
+      rewardMintPda,
+      user.publicKey
+    );
+    const positionBefore = await program.account.stakeAccount.fetch(
+      stakeAccount
+    );
+    const pointsBefore = (await program.account.userAccount.fetch(userAccount)).points;
+    const balanceBefore = new BN(
+      (await connection.getTokenAccountBalance(userRewardAtaAccount.address)).value.amount
+    );
+
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const PAUSE_CLAIM = 1 << 7;
+    await program.methods
+      .setPaused(PAUSE_CLAIM)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
     try {
-      const tx = await program.methods
-        .initializeConfig(8, 4, 2, 86400)
+      await program.methods
+        .claimRewards()
         .accountsPartial({
-          admin: admin.publicKey,
-          config: config,
+          user: user.publicKey,
           rewardMint: rewardMintPda,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
+          userRewardAta: userRewardAtaAccount.address,
+          rewardTreasury: null,
+          stakeAccount,
+          config,
+          userAccount,
+          allowedCollection: null,
         })
-        .signers([admin])
+        .remainingAccounts(await extraRewardAccounts())
+        .signers([user])
         .rpc();
-
-      await confirm(tx);
-      await log(tx);
-
-      console.log("Config initialized successfully!");
-      const configAccount = await program.account.stakeConfigAccount.fetch(
-        config
-      );
-      console.log("Config account data:", configAccount);
+      throw new Error("claiming should fail while claims are paused");
     } catch (error) {
-      console.error("Error initializing config:", error);
-      throw error;
+      if (!error.toString().includes("ClaimPaused")) {
+        throw error;
+      }
     }
-  });
+    await program.methods
+      .setPaused(0)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
 
-  it("initialize user", async () => {
     const txSig = await program.methods
-      .initializeUser()
+      .claimRewards()
       .accountsPartial({
         user: user.publicKey,
+        rewardMint: rewardMintPda,
+        userRewardAta: userRewardAtaAccount.address,
+        rewardTreasury: null,
+        stakeAccount,
+        config,
         userAccount,
-        systemProgram: SystemProgram.programId,
+        allowedCollection: null,
       })
+      .remainingAccounts(await extraRewardAccounts())
       .signers([user])
       .rpc();
     await confirm(txSig);
     await log(txSig);
+
+    const userAcc = await program.account.userAccount.fetch(userAccount);
+    const balanceAfter = new BN(
+      (await connection.getTokenAccountBalance(userRewardAtaAccount.address)).value.amount
+    );
+    const paid = balanceAfter.sub(balanceBefore);
+    if (paid.isZero() || !userAcc.points.sub(pointsBefore).eq(paid)) {
+      throw new Error("the claim should pay the primary reward and credit it as points");
+    }
+
+    // the funded partner token is paid, the unfunded one is left for a later claim
+    const positionAfter = await program.account.stakeAccount.fetch(stakeAccount);
+    if (positionAfter.extraRewardDebts[0].lte(positionBefore.extraRewardDebts[0])) {
+      throw new Error("the funded extra reward should be settled by the claim");
+    }
+    if (!positionAfter.extraRewardDebts[1].eq(positionBefore.extraRewardDebts[1])) {
+      throw new Error("an extra reward without budget should stay unsettled");
+    }
   });
 
-  it("stake sol", async () => {
+  it("tops up the open sol position", async () => {
     const userRewardAtaAccount = await getOrCreateAssociatedTokenAccount(
       connection,
       user,
       rewardMintPda,
       user.publicKey
     );
+    const positionBefore = await program.account.stakeAccount.fetch(
+      stakeAccount
+    );
+    try {
+      await program.methods
+        .increaseStakeSol(new BN(0))
+        .accountsPartial({
+          user: user.publicKey,
+          rewardMint: rewardMintPda,
+          userRewardAta: userRewardAtaAccount.address,
+          rewardTreasury: null,
+          stakeAccount,
+          config,
+          vault,
+          userAccount,
+        })
+        .remainingAccounts(await extraRewardAccounts())
+        .signers([user])
+        .rpc();
+      throw new Error("an empty top-up should have been rejected");
+    } catch (error) {
+      if (!error.toString().includes("InvalidStakeAmount")) {
+        throw error;
+      }
+    }
 
-    const stakeAmount = 1 * LAMPORTS_PER_SOL;
-    const txSig = await program.methods
-      .stakeSol(seed,new anchor.BN(stakeAmount))
+    await program.methods
+      .increaseStakeSol(new BN(LAMPORTS_PER_SOL / 2))
       .accountsPartial({
         user: user.publicKey,
         rewardMint: rewardMintPda,
-        config: config,
-        stakeAccount,
-        userAccount,
         userRewardAta: userRewardAtaAccount.address,
+        rewardTreasury: null,
+        stakeAccount,
+        config,
         vault,
-        systemProgram: SystemProgram.programId,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        userAccount,
       })
+      .remainingAccounts(await extraRewardAccounts())
       .signers([user])
-      .rpc();
-    await confirm(txSig);
-    await log(txSig);
+      .rpc()
+      .then(confirm);
+
+    const position = await program.account.stakeAccount.fetch(stakeAccount);
+    if (position.amount.toNumber() !== (3 * LAMPORTS_PER_SOL) / 2) {
+      throw new Error("top-up should add to the position amount");
+    }
+    if (position.unlockAt.lt(positionBefore.unlockAt)) {
+      throw new Error("top-up should never bring the unlock time forward");
+    }
+    if (position.lockDuration === 0 || position.lockDuration !== positionBefore.lockDuration) {
+      throw new Error("top-up should keep the tier lock duration of the position");
+    }
+    const now = Math.floor(Date.now() / 1000);
+    if (position.unlockAt.toNumber() > now + position.lockDuration + 60) {
+      throw new Error("top-up should lock no longer than the tier duration from now");
+    }
   });
 
-  it("stake spl", async () => {
-    // Create a new SPL mint
-    splMint = await createMint(
+  it("requires the two phase exit while an unbonding period is set", async () => {
+    await program.methods
+      .setUnbondingPeriod(3 * 86400)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    const userRewardAtaAccount = await getOrCreateAssociatedTokenAccount(
       connection,
-      admin,
-      admin.publicKey,
-      null,
-      6 // decimals
+      user,
+      rewardMintPda,
+      user.publicKey
     );
+    try {
+      await program.methods
+        .unstakeSol(new BN(LAMPORTS_PER_SOL))
+        .accountsPartial({
+          user: user.publicKey,
+          mint: rewardMintPda,
+          rewardMint: rewardMintPda,
+          userRewardAta: userRewardAtaAccount.address,
+          rewardTreasury: null,
+          stakeAccount,
+          config,
+          vault,
+          userAccount,
+        })
+        .remainingAccounts(await extraRewardAccounts())
+        .signers([user])
+        .rpc();
+      throw new Error("unstake sol should require request_unstake");
+    } catch (error) {
+      if (!error.toString().includes("UnbondingRequired")) {
+        throw error;
+      }
+    }
 
-    // Create user's ATA for the SPL mint
-    const userSplAta = await getOrCreateAssociatedTokenAccount(
+    await program.methods
+      .setUnbondingPeriod(0)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+  });
+
+  it("withdraws sol and spl positions only after the unbonding cooldown", async () => {
+    const day = 86400;
+    const lockTiers = [
+      { duration: 7 * day, multiplierBps: 10_000 },
+      { duration: 30 * day, multiplierBps: 12_500 },
+      { duration: 90 * day, multiplierBps: 15_000 },
+      { duration: 400 * day, multiplierBps: 25_000 },
+    ];
+    // an unlocked week tier lets fresh positions request their exit right away
+    await program.methods
+      .setLockTiers([{ duration: 0, multiplierBps: 10_000 }, ...lockTiers.slice(1)])
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+    await program.methods
+      .updateConfig(null, null, null, 0, null)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+    await program.methods
+      .setUnbondingPeriod(2)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    const userRewardAtaAccount = await getOrCreateAssociatedTokenAccount(
       connection,
       user,
-      splMint,
+      rewardMintPda,
       user.publicKey
     );
+    const userSplAta = getAssociatedTokenAddressSync(splMint, user.publicKey);
 
-    // Mint SPL tokens to user
-    await mintTo(
-      connection,
-      admin,
-      splMint,
-      userSplAta.address,
-      admin,
-      1_000_000 // 1 token (with 6 decimals)
+    const solSeed = new BN(randomBytes(8));
+    const [solStake] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        config.toBuffer(),
+        user.publicKey.toBuffer(),
+        solSeed.toArrayLike(Buffer, "le", 8),
+      ],
+      programId
     );
+    const [solVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), solStake.toBuffer()],
+      programId
+    );
+    await program.methods
+      .stakeSol(solSeed, new anchor.BN(LAMPORTS_PER_SOL / 10), { week: {} })
+      .accountsPartial({
+        user: user.publicKey,
+        config,
+        stakeAccount: solStake,
+        userAccount,
+        vault: solVault,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
 
-    // Derive stake_account PDA for SPL
-    [splStakeAccount] = PublicKey.findProgramAddressSync(
+    const splSeed = new BN(randomBytes(8));
+    const [splStake] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("stake"),
         config.toBuffer(),
         user.publicKey.toBuffer(),
         splMint.toBuffer(),
+        splSeed.toArrayLike(Buffer, "le", 8),
       ],
       programId
     );
+    const splVault = getAssociatedTokenAddressSync(splMint, splStake, true);
+    await program.methods
+      .stakeSpl(splSeed, new anchor.BN(100_000), { week: {} })
+      .accountsPartial({
+        user: user.publicKey,
+        mint: splMint,
+        allowedSplMint,
+        mintAta: userSplAta,
+        stakeAccount: splStake,
+        config,
+        vaultAta: splVault,
+        userAccount,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
 
-    // Derive vault_ata PDA for SPL
-    vaultAta = getAssociatedTokenAddressSync(
-      splMint,
-      splStakeAccount,
-      true
+    // a locked position leaving early pays its penalty and then waits out the same cooldown
+    const lockedSeed = new BN(randomBytes(8));
+    const [lockedStake] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stake"),
+        config.toBuffer(),
+        user.publicKey.toBuffer(),
+        lockedSeed.toArrayLike(Buffer, "le", 8),
+      ],
+      programId
     );
-
-    // User's reward ATA for reward mint
-    const userRewardAtaAccount = await getOrCreateAssociatedTokenAccount(
-      connection,
-      user,
-      rewardMintPda,
-      user.publicKey
+    const [lockedVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), lockedStake.toBuffer()],
+      programId
+    );
+    const [penaltyVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("penalty"), config.toBuffer()],
+      programId
     );
+    await program.methods
+      .stakeSol(lockedSeed, new anchor.BN(LAMPORTS_PER_SOL / 10), { month: {} })
+      .accountsPartial({
+        user: user.publicKey,
+        config,
+        stakeAccount: lockedStake,
+        userAccount,
+        vault: lockedVault,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+    const { earlyUnstakePenaltyBps } = await program.account.stakeConfigAccount.fetch(config);
+    await program.methods
+      .earlyUnstakeSol()
+      .accountsPartial({
+        user: user.publicKey,
+        stakeAccount: lockedStake,
+        config,
+        vault: lockedVault,
+        penaltyVault,
+        userAccount,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirm);
+    const lockedPosition = await program.account.stakeAccount.fetch(lockedStake);
+    const lockedPrincipal = (LAMPORTS_PER_SOL / 10) * (1 - earlyUnstakePenaltyBps / 10_000);
+    if (
+      lockedPosition.withdrawableAt.toNumber() === 0 ||
+      lockedPosition.amount.toNumber() !== lockedPrincipal
+    ) {
+      throw new Error("an early exit should keep the penalty and start the cooldown");
+    }
 
-    const stakeAmount = 500_000; // 0.5 SPL token
-    const txSig = await program.methods
-      .stakeSpl(new anchor.BN(12345), new anchor.BN(stakeAmount))
+    for (const position of [solStake, splStake]) {
+      await program.methods
+        .requestUnstake()
+        .accountsPartial({
+          user: user.publicKey,
+          rewardMint: rewardMintPda,
+          userRewardAta: userRewardAtaAccount.address,
+          rewardTreasury: null,
+          stakeAccount: position,
+          config,
+          userAccount,
+        })
+        .remainingAccounts(await extraRewardAccounts())
+        .signers([user])
+        .rpc()
+        .then(confirm);
+    }
+
+    const withdrawSol = () =>
+      program.methods
+        .withdrawSol()
+        .accountsPartial({
+          user: user.publicKey,
+          stakeAccount: solStake,
+          config,
+          vault: solVault,
+          userAccount,
+        })
+        .signers([user])
+        .rpc();
+    const withdrawSpl = () =>
+      program.methods
+        .withdrawSpl()
+        .accountsPartial({
+          user: user.publicKey,
+          mint: splMint,
+          allowedSplMint,
+          mintAta: userSplAta,
+          stakeAccount: splStake,
+          config,
+          vaultAta: splVault,
+          userAccount,
+        })
+        .signers([user])
+        .rpc();
+
+    for (const withdraw of [withdrawSol, withdrawSpl]) {
+      try {
+        await withdraw();
+        throw new Error("withdraw should wait for the cooldown");
+      } catch (error) {
+        if (!error.toString().includes("CooldownNotPassed")) {
+          throw error;
+        }
+      }
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    const PAUSE_UNSTAKE_SOL = 1 << 5;
+    await program.methods
+      .setPaused(PAUSE_UNSTAKE_SOL)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+    try {
+      await withdrawSol();
+      throw new Error("withdraw sol should respect the unstake pause");
+    } catch (error) {
+      if (!error.toString().includes("UnstakeSolPaused")) {
+        throw error;
+      }
+    }
+    await program.methods
+      .setPaused(0)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+
+    await withdrawSol().then(confirm);
+    await withdrawSpl().then(confirm);
+    const userBefore = await connection.getBalance(user.publicKey);
+    await program.methods
+      .withdrawSol()
       .accountsPartial({
         user: user.publicKey,
-        mint: splMint,
-        mintAta: userSplAta.address,
-        rewardMint: rewardMintPda,
-        userRewardAta: userRewardAtaAccount.address,
-        stakeAccount: splStakeAccount,
-        config: config,
-        vaultAta: vaultAta,
-        userAccount: userAccount,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
+        stakeAccount: lockedStake,
+        config,
+        vault: lockedVault,
+        userAccount,
       })
       .signers([user])
-      .rpc();
-    await confirm(txSig);
-    await log(txSig);
-    console.log("SPL staked successfully!");
-    const userAcc = await program.account.userAccount.fetch(userAccount);
-    console.log("User account after SPL stake:", userAcc);
+      .rpc()
+      .then(confirm);
+    if ((await connection.getBalance(user.publicKey)) - userBefore < lockedPrincipal) {
+      throw new Error("the early exit should pay out the rest of its principal after the cooldown");
+    }
+    if (
+      (await connection.getAccountInfo(solStake)) !== null ||
+      (await connection.getAccountInfo(splStake)) !== null ||
+      (await connection.getAccountInfo(lockedStake)) !== null
+    ) {
+      throw new Error("withdrawn positions should be closed");
+    }
+
+    await program.methods
+      .setUnbondingPeriod(0)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+    await program.methods
+      .updateConfig(null, null, null, day, null)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
+    await program.methods
+      .setLockTiers(lockTiers)
+      .accountsPartial({ admin: admin.publicKey, config })
+      .signers([admin])
+      .rpc()
+      .then(confirm);
   });
 
   it("unstake sol", async () => {
@@ -297,12 +2260,13 @@ describe("staking_nft_token", () => {
     //   true
     // )
     const txSig = await program.methods
-      .unstakeSol()
+      .unstakeSol(new BN(LAMPORTS_PER_SOL / 2))
       .accountsPartial({
         user: user.publicKey,
         mint: rewardMintPda, // using rewardMint as placeholder, adjust if needed
         rewardMint: rewardMintPda,
         userRewardAta: userRewardAtaAccount.address,
+        rewardTreasury: null,
         stakeAccount: stakeAccount,
         config: config,
         vault,
@@ -310,13 +2274,21 @@ describe("staking_nft_token", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
+      .remainingAccounts(await extraRewardAccounts())
       .signers([user])
       .rpc();
     await confirm(txSig);
     await log(txSig);
+    const position = await program.account.stakeAccount.fetch(stakeAccount);
+    if (position.amount.toNumber() !== LAMPORTS_PER_SOL) {
+      throw new Error("the rest of the sol position should remain staked");
+    }
     console.log("SOL unstaked successfully!");
     const userAcc = await program.account.userAccount.fetch(userAccount);
     console.log("User account after SOL unstake:", userAcc);
+    if (userAcc.solStakedAmount.toNumber() !== LAMPORTS_PER_SOL) {
+      throw new Error("user sol total should follow the unstaked amount");
+    }
   });
 
   it("unstake spl", async () => {
@@ -328,14 +2300,6 @@ describe("staking_nft_token", () => {
       user.publicKey
     );
 
-    splMint = await createMint(
-      connection,
-      admin,
-      admin.publicKey,
-      null,
-      6 // decimals
-    );
-
     // Create user's ATA for the SPL mint
     const userSplAta = await getOrCreateAssociatedTokenAccount(
       connection,
@@ -344,15 +2308,16 @@ describe("staking_nft_token", () => {
       user.publicKey
     );
 
-
     const txSig = await program.methods
-      .unstakeSpl()
+      .unstakeSpl(new BN(500_000))
       .accountsPartial({
         user: user.publicKey,
         mint: splMint, // Using the stored splMint
+        allowedSplMint,
         mintAta: userSplAta.address, // Using the stored userSplAta
         rewardMint: rewardMintPda,
         userRewardAta: userRewardAtaAccount.address,
+        rewardTreasury: null,
         stakeAccount: splStakeAccount, // Using the stored splStakeAccount
         config: config,
         vaultAta: vaultAta, // Using the stored vaultAta
@@ -360,6 +2325,7 @@ describe("staking_nft_token", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
+      .remainingAccounts(await extraRewardAccounts())
       .signers([user])
       .rpc();
     await confirm(txSig);
@@ -368,4 +2334,53 @@ describe("staking_nft_token", () => {
     const userAcc = await program.account.userAccount.fetch(userAccount);
     console.log("User account after SPL unstake:", userAcc);
   });
-});
\ No newline at end of file
+
+  it("keeps the user account open while positions are staked", async () => {
+    try {
+      await program.methods
+        .closeUser()
+        .accountsPartial({ user: user.publicKey, userAccount })
+        .signers([user])
+        .rpc();
+      throw new Error("close user should fail with open positions");
+    } catch (error) {
+      if (!error.toString().includes("UserHasOpenPositions")) {
+        throw error;
+      }
+    }
+  });
+
+  it("closes an empty user account and refunds its rent", async () => {
+    const leaver = Keypair.generate();
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: provider.publicKey,
+          toPubkey: leaver.publicKey,
+          lamports: LAMPORTS_PER_SOL / 10,
+        })
+      )
+    );
+    const [leaverAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), leaver.publicKey.toBuffer()],
+      programId
+    );
+
+    await program.methods
+      .initializeUser()
+      .accountsPartial({ user: leaver.publicKey, userAccount: leaverAccount })
+      .signers([leaver])
+      .rpc()
+      .then(confirm);
+    await program.methods
+      .closeUser()
+      .accountsPartial({ user: leaver.publicKey, userAccount: leaverAccount })
+      .signers([leaver])
+      .rpc()
+      .then(confirm);
+
+    if ((await connection.getAccountInfo(leaverAccount)) !== null) {
+      throw new Error("user account should be closed");
+    }
+  });
+});